@@ -1,6 +1,9 @@
 use std::{
+    cell::RefCell,
     convert::From,
+    mem,
     ops::{Deref, DerefMut},
+    ptr::NonNull,
 };
 
 mod arena;
@@ -11,11 +14,107 @@ use bumpalo::Bump;
 #[derive(Default)]
 pub struct Allocator {
     bump: Bump,
+    /// Values allocated with [`Allocator::alloc_with_drop`], to be dropped on drop or reset
+    drops: RefCell<std::vec::Vec<DropEntry>>,
+}
+
+/// Pointer to a value allocated with [`Allocator::alloc_with_drop`], and function to drop it.
+struct DropEntry {
+    ptr: NonNull<u8>,
+    drop_fn: unsafe fn(NonNull<u8>),
+}
+
+#[allow(unsafe_code)]
+// SAFETY: `Allocator::alloc_with_drop` only accepts `T: Send`, so values can be dropped on any thread
+unsafe impl Send for DropEntry {}
+
+impl Allocator {
+    /// Allocate `value`, and register it to be dropped when the `Allocator` is dropped or reset.
+    ///
+    /// `Bump::alloc` never runs destructors, so e.g. a std `Vec` allocated with it leaks its heap
+    /// allocation. Use this method instead for types which need cleanup.
+    ///
+    /// If `T` does not need dropping, this is the same as `Bump::alloc`.
+    /// Otherwise, each value costs a separate heap allocation, plus an entry (2 pointers) in a drop list.
+    /// The value is not stored in the arena, so resetting the arena by calling `Bump::reset` directly
+    /// cannot free memory of a value which is yet to be dropped.
+    /// Prefer arena types (e.g. [`Vec`], [`Box`]), which need no dropping.
+    ///
+    /// `T` must be `'static`, as it's dropped after any borrows it could hold would have expired.
+    #[allow(unsafe_code, clippy::mut_from_ref)]
+    pub fn alloc_with_drop<T: Send + 'static>(&self, value: T) -> &mut T {
+        /// # SAFETY
+        /// `ptr` must be from a leaked `std::boxed::Box<T>`, and not used again after this call.
+        unsafe fn drop_value<T>(ptr: NonNull<u8>) {
+            // SAFETY: Caller guarantees `ptr` is from a leaked `Box<T>`, and not used again
+            drop(unsafe { std::boxed::Box::from_raw(ptr.cast::<T>().as_ptr()) });
+        }
+
+        if !mem::needs_drop::<T>() {
+            return self.bump.alloc(value);
+        }
+
+        let ptr = NonNull::from(std::boxed::Box::leak(std::boxed::Box::new(value)));
+        self.drops.borrow_mut().push(DropEntry { ptr: ptr.cast(), drop_fn: drop_value::<T> });
+        // SAFETY: `ptr` was just allocated and initialized, and is not aliased.
+        // It's only used again by `run_drops`, which requires `&mut self`, so this reference
+        // must have expired by then.
+        unsafe { &mut *ptr.as_ptr() }
+    }
+
+    /// Drop all values allocated with [`Allocator::alloc_with_drop`], and then reset the arena.
+    ///
+    /// Calling `Bump::reset` directly (via `DerefMut`) resets the arena without running destructors.
+    /// Values allocated with [`Allocator::alloc_with_drop`] are then dropped later,
+    /// when the `Allocator` is dropped or this method is called.
+    pub fn reset(&mut self) {
+        self.run_drops();
+        self.bump.reset();
+    }
+
+    /// Drop all values allocated with [`Allocator::alloc_with_drop`], in order of allocation.
+    #[allow(unsafe_code)]
+    fn run_drops(&mut self) {
+        for entry in self.drops.get_mut().drain(..) {
+            // SAFETY: Entry was created by `alloc_with_drop` with matching `drop_fn` for its type.
+            // Value is on the heap, not in the arena, so it's still valid even if arena was reset.
+            // `&mut self` guarantees no references to the value are live.
+            // Entry is removed from drop list, so value is not dropped twice.
+            unsafe { (entry.drop_fn)(entry.ptr) };
+        }
+    }
+
+    /// Allocate a slice in arena, filled with items from an iterator.
+    ///
+    /// If the iterator reports an exact size hint (lower bound equal to upper bound),
+    /// the slice is allocated in one go, and filled in place.
+    /// Otherwise, items are collected into an arena `Vec`, which may need to grow as it's filled,
+    /// leaving its previous allocations unused in the arena.
+    ///
+    /// Unlike `Bump::alloc_slice_fill_iter`, does not require an `ExactSizeIterator`.
+    ///
+    /// # Panics
+    /// Panics if iterator reports an exact size hint, but yields fewer items than that.
+    pub fn alloc_slice_fill_iter<T, I: IntoIterator<Item = T>>(&self, iter: I) -> &mut [T] {
+        let mut iter = iter.into_iter();
+        match iter.size_hint() {
+            (len, Some(max_len)) if len == max_len => self.bump.alloc_slice_fill_with(len, |_| {
+                iter.next().expect("Iterator supplied too few elements")
+            }),
+            _ => bumpalo::collections::Vec::from_iter_in(iter, &self.bump).into_bump_slice_mut(),
+        }
+    }
 }
 
 impl From<Bump> for Allocator {
     fn from(bump: Bump) -> Self {
-        Self { bump }
+        Self { bump, drops: RefCell::default() }
+    }
+}
+
+impl Drop for Allocator {
+    fn drop(&mut self) {
+        self.run_drops();
     }
 }
 
@@ -35,11 +134,127 @@ impl DerefMut for Allocator {
 
 #[cfg(test)]
 mod test {
-    use std::ops::Deref;
+    use std::{
+        cell::Cell,
+        ops::{Deref, DerefMut},
+        rc::Rc,
+    };
 
     use crate::Allocator;
     use bumpalo::Bump;
 
+    #[test]
+    fn alloc_slice_fill_iter() {
+        let allocator = Allocator::default();
+
+        // Exact size iterator allocates once, so slice is directly between 2 adjacent allocations.
+        // `Bump` allocates downwards.
+        let before = allocator.alloc(0u64) as *const u64 as usize;
+        let slice = allocator.alloc_slice_fill_iter(0..10u64);
+        assert_eq!(slice, &[0, 1, 2, 3, 4, 5, 6, 7, 8, 9]);
+        let slice_addr = slice.as_ptr() as usize;
+        let after = allocator.alloc(0u64) as *const u64 as usize;
+        assert_eq!(slice_addr + 10 * 8, before);
+        assert_eq!(after + 8, slice_addr);
+
+        // Iterator without exact size hint
+        let slice = allocator.alloc_slice_fill_iter((0..20u64).filter(|n| n % 3 == 0));
+        assert_eq!(slice, &[0, 3, 6, 9, 12, 15, 18]);
+
+        // Empty
+        let slice = allocator.alloc_slice_fill_iter(std::iter::empty::<u64>());
+        assert!(slice.is_empty());
+        let slice = allocator.alloc_slice_fill_iter((0..10u64).filter(|_| false));
+        assert!(slice.is_empty());
+    }
+
+    #[test]
+    #[should_panic(expected = "Iterator supplied too few elements")]
+    fn alloc_slice_fill_iter_too_few() {
+        struct Lying(u32);
+        impl Iterator for Lying {
+            type Item = u32;
+            fn next(&mut self) -> Option<u32> {
+                self.0 = self.0.checked_sub(1)?;
+                Some(self.0)
+            }
+            fn size_hint(&self) -> (usize, Option<usize>) {
+                (10, Some(10))
+            }
+        }
+
+        let allocator = Allocator::default();
+        allocator.alloc_slice_fill_iter(Lying(5));
+    }
+
+    #[test]
+    fn alloc_slice_clone() {
+        // `Clone` but not `Copy`
+        #[derive(Clone, Debug, PartialEq)]
+        struct Node {
+            name: String,
+            children: Vec<u32>,
+        }
+
+        let allocator = Allocator::default();
+        let src = vec![
+            Node { name: "a".to_string(), children: vec![1, 2] },
+            Node { name: "b".to_string(), children: vec![] },
+            Node { name: "c".to_string(), children: vec![3] },
+        ];
+        let cloned = allocator.alloc_slice_clone(&src);
+        assert_eq!(cloned, src.as_slice());
+        assert_ne!(cloned.as_ptr(), src.as_ptr());
+        for (cloned, original) in cloned.iter().zip(&src) {
+            assert_ne!(cloned.name.as_ptr(), original.name.as_ptr());
+        }
+
+        let empty: &[Node] = &[];
+        assert!(allocator.alloc_slice_clone(empty).is_empty());
+    }
+
+    #[test]
+    fn alloc_with_drop() {
+        struct Counted(Rc<Cell<u32>>);
+        impl Drop for Counted {
+            fn drop(&mut self) {
+                self.0.set(self.0.get() + 1);
+            }
+        }
+
+        // `Rc` is not `Send`, so wrap the counter in a type which is
+        struct SendCounted(Counted);
+        #[allow(unsafe_code)]
+        // SAFETY: Test is single-threaded
+        unsafe impl Send for SendCounted {}
+
+        let count = Rc::new(Cell::new(0));
+        let mut allocator = Allocator::default();
+        let value = allocator.alloc_with_drop(SendCounted(Counted(Rc::clone(&count))));
+        assert_eq!(Rc::strong_count(&value.0 .0), 2);
+        let vec = allocator.alloc_with_drop(vec![1, 2, 3]);
+        vec.push(4);
+        assert_eq!(vec, &[1, 2, 3, 4]);
+        assert_eq!(count.get(), 0);
+
+        // Reset drops values
+        allocator.reset();
+        assert_eq!(count.get(), 1);
+
+        allocator.alloc_with_drop(SendCounted(Counted(Rc::clone(&count))));
+        allocator.alloc_with_drop(SendCounted(Counted(Rc::clone(&count))));
+        assert_eq!(count.get(), 1);
+
+        // Resetting `Bump` directly does not drop values, but they're still dropped later
+        allocator.deref_mut().reset();
+        assert_eq!(count.get(), 1);
+
+        // Drop drops values, exactly once
+        drop(allocator);
+        assert_eq!(count.get(), 3);
+        assert_eq!(Rc::strong_count(&count), 1);
+    }
+
     #[test]
     fn test_api() {
         let bump = Bump::new();
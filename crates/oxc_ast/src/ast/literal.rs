@@ -209,8 +209,14 @@ impl TryFrom<char> for RegExpFlags {
     }
 }
 
+/// Flags are printed in canonical order `dgimsuvy`, regardless of order in source,
+/// same as `RegExp.prototype.flags`.
+/// <https://tc39.es/ecma262/#sec-get-regexp.prototype.flags>
 impl fmt::Display for RegExpFlags {
     fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        if self.contains(Self::D) {
+            write!(f, "d")?;
+        }
         if self.contains(Self::G) {
             write!(f, "g")?;
         }
@@ -226,15 +232,12 @@ impl fmt::Display for RegExpFlags {
         if self.contains(Self::U) {
             write!(f, "u")?;
         }
-        if self.contains(Self::Y) {
-            write!(f, "y")?;
-        }
-        if self.contains(Self::D) {
-            write!(f, "d")?;
-        }
         if self.contains(Self::V) {
             write!(f, "v")?;
         }
+        if self.contains(Self::Y) {
+            write!(f, "y")?;
+        }
         Ok(())
     }
 }
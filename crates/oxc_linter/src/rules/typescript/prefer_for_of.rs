@@ -46,11 +46,11 @@ declare_oxc_lint!(
 );
 
 trait SpanExt {
-    fn contains(&self, other: Self) -> bool;
+    fn contains_span(&self, other: Self) -> bool;
 }
 
 impl SpanExt for Span {
-    fn contains(&self, other: Self) -> bool {
+    fn contains_span(&self, other: Self) -> bool {
         self.start <= other.start && self.end >= other.end
     }
 }
@@ -183,7 +183,7 @@ impl Rule for PreferForOf {
             let ref_id = reference.node_id();
 
             let symbol_span = nodes.get_node(ref_id).kind().span();
-            if !body_span.contains(symbol_span) {
+            if !body_span.contains_span(symbol_span) {
                 return false;
             }
 
@@ -94,7 +94,7 @@ impl Rule for PreferQuerySelector {
             }
 
             let literal_value = match argument_expr {
-                Expression::StringLiteral(literal) => Some(literal.value.trim()),
+                Expression::StringLiteral(literal) => Some(literal.value.as_str().trim()),
                 Expression::TemplateLiteral(literal) => {
                     if literal.expressions.len() == 0 {
                         literal.quasis.first().unwrap().value.cooked.as_deref().map(str::trim)
@@ -43,13 +43,8 @@ impl<'a> ParserImpl<'a> {
 
     /// Get current source text
     pub(crate) fn cur_src(&self) -> &'a str {
-        let range = self.cur_token().span();
-        // SAFETY:
-        // range comes from the parser, which are ensured to meeting the criteria of `get_unchecked`.
-        #[allow(unsafe_code)]
-        unsafe {
-            self.source_text.get_unchecked(range.start as usize..range.end as usize)
-        }
+        let token = self.cur_token();
+        self.lexer.source_text_between(token.start, token.end)
     }
 
     /// Get current string
@@ -59,7 +54,12 @@ impl<'a> ParserImpl<'a> {
 
     /// Get current template string
     pub(crate) fn cur_template_string(&self) -> Option<&'a str> {
-        self.lexer.get_template_string(self.token)
+        self.lexer.template_cooked(self.token)
+    }
+
+    /// Get raw value of current template string
+    pub(crate) fn cur_template_raw(&self) -> &'a str {
+        self.lexer.template_raw(self.token)
     }
 
     /// Peek next token, returns EOF for final peek
@@ -105,7 +105,7 @@ impl<'a> ParserImpl<'a> {
     /// whose code point sequence is the same as a `ReservedWord`.
     #[inline]
     fn test_escaped_keyword(&mut self, kind: Kind) {
-        if self.cur_token().escaped() && kind.is_all_keyword() {
+        if self.cur_token().is_escaped() && kind.is_keyword() {
             let span = self.cur_token().span();
             self.error(diagnostics::escaped_keyword(span));
         }
@@ -178,7 +178,7 @@ impl<'a> ParserImpl<'a> {
     pub(crate) fn expect_without_advance(&mut self, kind: Kind) -> Result<()> {
         if !self.at(kind) {
             let range = self.cur_token().span();
-            return Err(diagnostics::expect_token(kind.to_str(), self.cur_kind().to_str(), range));
+            return Err(diagnostics::expect_token(kind.as_str(), self.cur_kind().as_str(), range));
         }
         Ok(())
     }
@@ -248,7 +248,14 @@ impl<'a> ParserImpl<'a> {
 
     pub(crate) fn re_lex_ts_r_angle(&mut self) {
         let kind = self.cur_kind();
-        if matches!(kind, Kind::ShiftRight | Kind::ShiftRight3) {
+        if matches!(
+            kind,
+            Kind::ShiftRight
+                | Kind::ShiftRight3
+                | Kind::GtEq
+                | Kind::ShiftRightEq
+                | Kind::ShiftRight3Eq
+        ) {
             self.token = self.lexer.re_lex_as_typescript_r_angle(kind);
         }
     }
@@ -45,11 +45,43 @@ pub fn invalid_character(x0: char, span1: Span) -> OxcDiagnostic {
     OxcDiagnostic::error(format!("Invalid Character `{x0}`")).with_labels([span1.into()])
 }
 
+#[cold]
+pub fn invalid_big_int(span0: Span) -> OxcDiagnostic {
+    OxcDiagnostic::error("Invalid BigInt literal")
+        .with_help("BigInt literals must be integers, without a decimal point or exponent")
+        .with_labels([span0.into()])
+}
+
 #[cold]
 pub fn invalid_number_end(span0: Span) -> OxcDiagnostic {
     OxcDiagnostic::error("Invalid characters after number").with_labels([span0.into()])
 }
 
+#[cold]
+pub fn missing_number_digits(x0: &str, span1: Span) -> OxcDiagnostic {
+    OxcDiagnostic::error(format!("Missing {x0} digits")).with_labels([span1.into()])
+}
+
+#[cold]
+pub fn unexpected_decimal_point(span0: Span) -> OxcDiagnostic {
+    OxcDiagnostic::error("Unexpected decimal point in number")
+        .with_help(
+            "A number can only contain one decimal point, which must come before any exponent",
+        )
+        .with_labels([span0.into()])
+}
+
+#[cold]
+pub fn numeric_separator_not_allowed(span0: Span) -> OxcDiagnostic {
+    OxcDiagnostic::error("Numeric separators are not allowed here").with_labels([span0.into()])
+}
+
+#[cold]
+pub fn consecutive_numeric_separators(span0: Span) -> OxcDiagnostic {
+    OxcDiagnostic::error("Only one underscore is allowed as numeric separator")
+        .with_labels([span0.into()])
+}
+
 #[cold]
 pub fn unterminated_multi_line_comment(span0: Span) -> OxcDiagnostic {
     OxcDiagnostic::error("Unterminated multiline comment").with_labels([span0.into()])
@@ -66,6 +98,14 @@ pub fn reg_exp_flag(x0: char, span1: Span) -> OxcDiagnostic {
         .with_labels([span1.into()])
 }
 
+#[cold]
+pub fn reg_exp_flag_u_and_v(span0: Span) -> OxcDiagnostic {
+    OxcDiagnostic::error(
+        "The 'u' and 'v' regular expression flags cannot be enabled at the same time",
+    )
+    .with_labels([span0.into()])
+}
+
 #[cold]
 pub fn reg_exp_flag_twice(x0: char, span1: Span) -> OxcDiagnostic {
     OxcDiagnostic::error(format!("Flag {x0} is mentioned twice in regular expression literal"))
@@ -352,6 +392,11 @@ pub fn return_statement_only_in_function_body(span0: Span) -> OxcDiagnostic {
         .with_labels([span0.into()])
 }
 
+#[cold]
+pub fn invalid_jsx_entity(x0: &str, span1: Span) -> OxcDiagnostic {
+    OxcDiagnostic::error(format!("Invalid HTML entity `&{x0};`")).with_labels([span1.into()])
+}
+
 #[cold]
 pub fn jsx_expressions_may_not_use_the_comma_operator(span0: Span) -> OxcDiagnostic {
     OxcDiagnostic::error("TS18007: JSX expressions may not use the comma operator.")
@@ -55,6 +55,9 @@ impl<'a> ParserImpl<'a> {
         r#type: ClassType,
         modifiers: Modifiers<'a>,
     ) -> Result<Box<'a, Class<'a>>> {
+        // All parts of a class are strict mode code
+        let strict = self.lexer.is_strict();
+        self.lexer.set_strict(true);
         self.bump_any(); // advance `class`
 
         let decorators = self.state.consume_decorators();
@@ -78,7 +81,7 @@ impl<'a> ParserImpl<'a> {
                 super_type_parameters = first_extends.1;
             }
         }
-        let body = self.parse_class_body()?;
+        let body = self.parse_class_body(strict)?;
 
         Ok(self.ast.class(
             r#type,
@@ -151,9 +154,10 @@ impl<'a> ParserImpl<'a> {
         Ok(extends)
     }
 
-    fn parse_class_body(&mut self) -> Result<Box<'a, ClassBody<'a>>> {
+    /// `strict` is whether code after the class is strict mode code.
+    fn parse_class_body(&mut self, strict: bool) -> Result<Box<'a, ClassBody<'a>>> {
         let span = self.start_span();
-        let mut class_elements = ClassElements::new(self);
+        let mut class_elements = ClassElements::new(self, strict);
         class_elements.parse(self)?;
         let body = class_elements.elements;
         Ok(self.ast.class_body(self.end_span(span), body))
@@ -338,7 +338,7 @@ impl<'a> ParserImpl<'a> {
         // split out pattern
         let (pattern_end, flags) = self.read_regex();
         let pattern_start = self.cur_token().start + 1; // +1 to exclude `/`
-        let pattern = &self.source_text[pattern_start as usize..pattern_end as usize];
+        let pattern = self.lexer.source_text_between(pattern_start, pattern_end);
 
         self.bump_any();
         self.ast.reg_exp_literal(self.end_span(span), pattern, flags)
@@ -454,14 +454,7 @@ impl<'a> ParserImpl<'a> {
         // `cooked = None` when template literal has invalid escape sequence
         // This is matched by `is_valid_escape_sequence` in `Lexer::read_template_literal`
         let cooked = self.cur_template_string();
-
-        let cur_src = self.cur_src();
-        let raw = &cur_src[1..cur_src.len() - end_offset as usize];
-        let raw = Atom::from(if cooked.is_some() && raw.contains('\r') {
-            self.ast.new_str(raw.replace("\r\n", "\n").replace('\r', "\n").as_str())
-        } else {
-            raw
-        });
+        let raw = Atom::from(self.cur_template_raw());
 
         self.bump_any();
 
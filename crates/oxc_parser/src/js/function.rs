@@ -50,17 +50,20 @@ impl<'a> ParserImpl<'a> {
     }
 
     pub(crate) fn at_async_no_new_line(&mut self) -> bool {
-        self.at(Kind::Async) && !self.cur_token().escaped() && !self.peek_token().is_on_new_line
+        self.at(Kind::Async) && !self.cur_token().is_escaped() && !self.peek_token().is_on_new_line
     }
 
     pub(crate) fn parse_function_body(&mut self) -> Result<Box<'a, FunctionBody<'a>>> {
         let span = self.start_span();
         self.expect(Kind::LCurly)?;
 
+        let strict = self.lexer.is_strict();
         let (directives, statements) = self.with_context(Context::Return, |p| {
             p.parse_directives_and_statements(/* is_top_level */ false)
         })?;
 
+        // Exit strict mode entered by a `"use strict"` directive before lexing the token after `}`
+        self.lexer.set_strict(strict);
         self.expect(Kind::RCurly)?;
         Ok(self.ast.function_body(self.end_span(span), directives, statements))
     }
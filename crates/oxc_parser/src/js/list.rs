@@ -377,11 +377,13 @@ impl<'a> SeparatedList<'a> for ExportNamedSpecifiers<'a> {
 
 pub struct ClassElements<'a> {
     pub elements: Vec<'a, ClassElement<'a>>,
+    /// Whether code after the class body is strict mode code
+    strict: bool,
 }
 
 impl<'a> ClassElements<'a> {
-    pub(crate) fn new(p: &ParserImpl<'a>) -> Self {
-        Self { elements: p.ast.new_vec() }
+    pub(crate) fn new(p: &ParserImpl<'a>, strict: bool) -> Self {
+        Self { elements: p.ast.new_vec(), strict }
     }
 }
 
@@ -407,6 +409,17 @@ impl<'a> NormalList<'a> for ClassElements<'a> {
         self.elements.push(element);
         Ok(())
     }
+
+    fn parse(&mut self, p: &mut ParserImpl<'a>) -> Result<()> {
+        p.expect(self.open())?;
+        while !p.at(self.close()) && !p.at(Kind::Eof) {
+            self.parse_element(p)?;
+        }
+        // Exit the class body's strict mode before lexing the token after `}`
+        p.lexer.set_strict(self.strict);
+        p.expect(self.close())?;
+        Ok(())
+    }
 }
 
 pub struct SwitchCases<'a> {
@@ -19,7 +19,7 @@ impl<'a> ParserImpl<'a> {
             let span = self.start_span();
             self.bump_any();
             let span = self.end_span(span);
-            let src = &self.source_text[span.start as usize + 2..span.end as usize];
+            let src = self.lexer.source_text_between(span.start + 2, span.end);
             Some(self.ast.hashbang(span, Atom::from(src)))
         } else {
             None
@@ -57,6 +57,8 @@ impl<'a> ParserImpl<'a> {
                     continue;
                 }
                 _ => {
+                    let checkpoint =
+                        (expecting_directives && self.at(Kind::Str)).then(|| self.checkpoint());
                     let stmt = self.parse_statement_list_item(StatementContext::StatementList)?;
 
                     // Section 11.2.1 Directive Prologue
@@ -67,8 +69,25 @@ impl<'a> ParserImpl<'a> {
                             if let Expression::StringLiteral(string) = &expr.expression {
                                 // span start will mismatch if they are parenthesized when `preserve_parens = false`
                                 if expr.span.start == string.span.start {
-                                    let src = &self.source_text[string.span.start as usize + 1
-                                        ..string.span.end as usize - 1];
+                                    let raw = self
+                                        .lexer
+                                        .source_text_between(string.span.start, string.span.end);
+                                    // Unterminated string has no closing quote (lexer reports the error)
+                                    let quote = &raw[..1];
+                                    let src = &raw[1..];
+                                    let src = src.strip_suffix(quote).unwrap_or(src);
+
+                                    // `"use strict"` makes the whole function or script strict mode code.
+                                    // Re-parse this directive in strict mode, so tokens after it
+                                    // are lexed again.
+                                    if src == "use strict" && !self.lexer.is_strict() {
+                                        if let Some(checkpoint) = checkpoint {
+                                            self.rewind(checkpoint);
+                                        }
+                                        self.lexer.set_strict(true);
+                                        continue;
+                                    }
+
                                     let directive = self.ast.directive(
                                         expr.span,
                                         (*string).clone(),
@@ -127,13 +146,24 @@ impl<'a> ParserImpl<'a> {
             Kind::Const if !(self.ts_enabled() && self.is_at_enum_declaration()) => {
                 self.parse_variable_statement(stmt_ctx)
             }
-            Kind::Let if !self.cur_token().escaped() => self.parse_let(stmt_ctx),
+            Kind::Let if !self.cur_token().is_escaped() => self.parse_let(stmt_ctx),
+            // `await [no LineTerminator here] using [no LineTerminator here] BindingList`
             Kind::Await
-                if self.peek_kind() == Kind::Using && self.nth_kind(2).is_binding_identifier() =>
+                if self.peek_kind() == Kind::Using
+                    && !self.peek_token().is_on_new_line
+                    && self.nth_kind(2).is_binding_identifier()
+                    && !self.nth(2).is_on_new_line =>
+            {
+                self.parse_using()
+            }
+            // `using [no LineTerminator here] BindingList`.
+            // Otherwise `using` is an identifier e.g. `using\nx = 1` is `using; x = 1;`.
+            Kind::Using
+                if self.peek_kind().is_binding_identifier()
+                    && !self.peek_token().is_on_new_line =>
             {
                 self.parse_using()
             }
-            Kind::Using if self.peek_kind().is_binding_identifier() => self.parse_using(),
             _ if self.at_function_with_async() => self.parse_function_declaration(stmt_ctx),
             _ if self.ts_enabled() && self.at_start_of_ts_declaration() => {
                 self.parse_ts_declaration_statement(start_span)
@@ -276,7 +306,7 @@ impl<'a> ParserImpl<'a> {
 
         let is_let_of = self.at(Kind::Let) && self.peek_at(Kind::Of);
         let is_async_of =
-            self.at(Kind::Async) && !self.cur_token().escaped() && self.peek_at(Kind::Of);
+            self.at(Kind::Async) && !self.cur_token().is_escaped() && self.peek_at(Kind::Of);
         let expr_span = self.start_span();
 
         if self.at(Kind::RParen) {
@@ -206,7 +206,7 @@ impl<'a> ParserImpl<'a> {
                 self.parse_jsx_fragment(true).map(JSXChild::Fragment).map(Some)
             }
             // <ident open element
-            Kind::LAngle if self.peek_at(Kind::Ident) || self.peek_kind().is_all_keyword() => {
+            Kind::LAngle if self.peek_at(Kind::Ident) || self.peek_kind().is_keyword() => {
                 self.parse_jsx_element(true).map(JSXChild::Element).map(Some)
             }
             // {...expr}
@@ -366,14 +366,14 @@ impl<'a> ParserImpl<'a> {
     ///   `JSXIdentifier` [no `WhiteSpace` or Comment here] -
     fn parse_jsx_identifier(&mut self) -> Result<JSXIdentifier<'a>> {
         let span = self.start_span();
-        if !self.at(Kind::Ident) && !self.cur_kind().is_all_keyword() {
+        if !self.at(Kind::Ident) && !self.cur_kind().is_keyword() {
             return Err(self.unexpected());
         }
         // Currently at a valid normal Ident or Keyword, keep on lexing for `-` in `<component-name />`
         self.continue_lex_jsx_identifier();
         self.bump_any();
         let span = self.end_span(span);
-        let name = span.source_text(self.source_text);
+        let name = self.lexer.source_text_between(span.start, span.end);
         Ok(self.ast.jsx_identifier(span, name.into()))
     }
 
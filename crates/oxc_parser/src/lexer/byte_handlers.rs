@@ -1,5 +1,4 @@
 use super::{Kind, Lexer};
-use crate::diagnostics;
 
 #[allow(clippy::unnecessary_safety_comment)]
 /// Handle next byte of source.
@@ -172,9 +171,13 @@ macro_rules! ascii_byte_handler {
 /// ```
 macro_rules! ascii_identifier_handler {
     ($id:ident($str:ident) $body:expr) => {
-        byte_handler!($id(lexer) {
+        ascii_identifier_handler!($id(lexer, $str) $body);
+    };
+
+    ($id:ident($lexer:ident, $str:ident) $body:expr) => {
+        byte_handler!($id($lexer) {
             // SAFETY: This macro is only used for ASCII characters
-            let $str = unsafe { lexer.identifier_name_handler() };
+            let $str = unsafe { $lexer.identifier_name_handler() };
             $body
         });
     };
@@ -183,8 +186,7 @@ macro_rules! ascii_identifier_handler {
 // `\0` `\1` etc
 ascii_byte_handler!(ERR(lexer) {
     let c = lexer.consume_char();
-    lexer.error(diagnostics::invalid_character(c, lexer.unterminated_range()));
-    Kind::Undetermined
+    lexer.invalid_characters(c)
 });
 
 // <SPACE> <TAB> Normal Whitespace
@@ -237,7 +239,8 @@ ascii_byte_handler!(HAS(lexer) {
     lexer.consume_char();
     // HashbangComment ::
     //     `#!` SingleLineCommentChars?
-    if lexer.token.start == 0 && lexer.next_eq('!') {
+    // Only valid at very start of file (after BOM, if there is one)
+    if lexer.token.start == lexer.source.program_start_offset() && lexer.next_eq('!') {
         lexer.read_hashbang_comment()
     } else {
         lexer.private_identifier()
@@ -389,17 +392,19 @@ ascii_byte_handler!(LSS(lexer) {
 // =
 ascii_byte_handler!(EQL(lexer) {
     lexer.consume_char();
-    if lexer.next_eq('=') {
-        if lexer.next_eq('=') {
-            Kind::Eq3
-        } else {
-            Kind::Eq2
-        }
-    } else if lexer.next_eq('>') {
-        Kind::Arrow
-    } else {
-        Kind::Eq
-    }
+    // Peek bytes to choose between `=`, `==`, `===` and `=>`, then consume them all in one go.
+    // `====` is `===` followed by `=`.
+    let (kind, len) = match lexer.source.peek_byte_at(0) {
+        Some(b'=') => match lexer.source.peek_byte_at(1) {
+            Some(b'=') => (Kind::Eq3, 2),
+            _ => (Kind::Eq2, 1),
+        },
+        Some(b'>') => (Kind::Arrow, 1),
+        _ => return Kind::Eq,
+    };
+    // SAFETY: Next `len` bytes have been peeked and are ASCII
+    unsafe { lexer.source.advance(len) };
+    kind
 });
 
 // >
@@ -420,7 +425,7 @@ ascii_byte_handler!(QST(lexer) {
         }
     } else if lexer.peek() == Some('.') {
         // parse `?.1` as `?` `.1`
-        if lexer.peek2().is_some_and(|c| c.is_ascii_digit()) {
+        if lexer.source.peek_byte_at(1).is_some_and(|b| b.is_ascii_digit()) {
             Kind::Question
         } else {
             lexer.consume_char();
@@ -565,14 +570,14 @@ ascii_identifier_handler!(L_G(id_without_first_char) match id_without_first_char
     _ => Kind::Ident,
 });
 
-ascii_identifier_handler!(L_I(id_without_first_char) match id_without_first_char {
+ascii_identifier_handler!(L_I(lexer, id_without_first_char) match id_without_first_char {
     "f" => Kind::If,
     "nstanceof" => Kind::Instanceof,
     "n" => Kind::In,
-    "mplements" => Kind::Implements,
+    "mplements" => lexer.keyword_for_strict_mode(Kind::Implements),
     "mport" => Kind::Import,
     "nfer" => Kind::Infer,
-    "nterface" => Kind::Interface,
+    "nterface" => lexer.keyword_for_strict_mode(Kind::Interface),
     "ntrinsic" => Kind::Intrinsic,
     "s" => Kind::Is,
     _ => Kind::Ident,
@@ -583,8 +588,8 @@ ascii_identifier_handler!(L_K(id_without_first_char) match id_without_first_char
     _ => Kind::Ident,
 });
 
-ascii_identifier_handler!(L_L(id_without_first_char) match id_without_first_char {
-    "et" => Kind::Let,
+ascii_identifier_handler!(L_L(lexer, id_without_first_char) match id_without_first_char {
+    "et" => lexer.keyword_for_ecma_version(Kind::Let),
     _ => Kind::Ident,
 });
 
@@ -611,11 +616,11 @@ ascii_identifier_handler!(L_O(id_without_first_char) match id_without_first_char
     _ => Kind::Ident,
 });
 
-ascii_identifier_handler!(L_P(id_without_first_char) match id_without_first_char {
-    "ackage" => Kind::Package,
-    "rivate" => Kind::Private,
-    "rotected" => Kind::Protected,
-    "ublic" => Kind::Public,
+ascii_identifier_handler!(L_P(lexer, id_without_first_char) match id_without_first_char {
+    "ackage" => lexer.keyword_for_strict_mode(Kind::Package),
+    "rivate" => lexer.keyword_for_strict_mode(Kind::Private),
+    "rotected" => lexer.keyword_for_strict_mode(Kind::Protected),
+    "ublic" => lexer.keyword_for_strict_mode(Kind::Public),
     _ => Kind::Ident,
 });
 
@@ -626,11 +631,11 @@ ascii_identifier_handler!(L_R(id_without_first_char) match id_without_first_char
     _ => Kind::Ident,
 });
 
-ascii_identifier_handler!(L_S(id_without_first_char) match id_without_first_char {
+ascii_identifier_handler!(L_S(lexer, id_without_first_char) match id_without_first_char {
     "et" => Kind::Set,
     "uper" => Kind::Super,
     "witch" => Kind::Switch,
-    "tatic" => Kind::Static,
+    "tatic" => lexer.keyword_for_strict_mode(Kind::Static),
     "ymbol" => Kind::Symbol,
     "tring" => Kind::String,
     "atisfies" => Kind::Satisfies,
@@ -668,8 +673,8 @@ ascii_identifier_handler!(L_W(id_without_first_char) match id_without_first_char
     _ => Kind::Ident,
 });
 
-ascii_identifier_handler!(L_Y(id_without_first_char) match id_without_first_char {
-    "ield" => Kind::Yield,
+ascii_identifier_handler!(L_Y(lexer, id_without_first_char) match id_without_first_char {
+    "ield" => lexer.keyword_for_ecma_version(Kind::Yield),
     _ => Kind::Ident,
 });
 
@@ -688,3 +693,58 @@ byte_handler!(UNI(lexer) {
 byte_handler!(UER(_lexer) {
     unreachable!();
 });
+
+#[cfg(test)]
+mod test {
+    use oxc_allocator::Allocator;
+    use oxc_span::Span;
+
+    use crate::lexer::{
+        tester::{error_spans, kinds, lex},
+        Kind,
+    };
+
+    #[test]
+    fn eq_punctuators() {
+        let allocator = Allocator::default();
+        for (source, expected) in [
+            ("a = b", &[(Kind::Eq, 2, 3)][..]),
+            ("a == b", &[(Kind::Eq2, 2, 4)]),
+            ("a === b", &[(Kind::Eq3, 2, 5)]),
+            ("a => b", &[(Kind::Arrow, 2, 4)]),
+            ("a =", &[(Kind::Eq, 2, 3)]),
+            ("a ==", &[(Kind::Eq2, 2, 4)]),
+            ("a ==> b", &[(Kind::Eq2, 2, 4), (Kind::RAngle, 4, 5)]),
+            ("a ==== b", &[(Kind::Eq3, 2, 5), (Kind::Eq, 5, 6)]),
+        ] {
+            let (tokens, lexer) = lex(&allocator, source);
+            let punctuators = tokens
+                .iter()
+                .filter(|token| token.kind != Kind::Ident)
+                .map(|token| (token.kind, token.start, token.end))
+                .collect::<Vec<_>>();
+            assert_eq!(punctuators, expected, "{source}");
+            assert!(lexer.errors.is_empty());
+        }
+    }
+
+    #[test]
+    fn invalid_character_run() {
+        let allocator = Allocator::default();
+        let (tokens, lexer) = lex(&allocator, "\u{1}\u{2}\u{3} x");
+        assert_eq!(kinds(&tokens), [Kind::Undetermined, Kind::Ident]);
+        assert_eq!(tokens[0].span(), Span::new(0, 3));
+        assert_eq!(error_spans(&lexer), [Span::new(0, 3)]);
+
+        // Multi-byte invalid chars
+        let (tokens, lexer) = lex(&allocator, "x ☃☃\u{1} y");
+        assert_eq!(kinds(&tokens), [Kind::Ident, Kind::Undetermined, Kind::Ident]);
+        assert_eq!(tokens[1].span(), Span::new(2, 9));
+        assert_eq!(lexer.errors.len(), 1);
+
+        // Runs separated by whitespace are reported separately
+        let (tokens, lexer) = lex(&allocator, "\u{1} \u{2}");
+        assert_eq!(kinds(&tokens), [Kind::Undetermined, Kind::Undetermined]);
+        assert_eq!(lexer.errors.len(), 2);
+    }
+}
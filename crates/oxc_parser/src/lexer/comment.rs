@@ -23,7 +23,11 @@ static MULTILINE_COMMENT_START_TABLE: SafeByteMatchTable =
 
 impl<'a> Lexer<'a> {
     /// Section 12.4 Single Line Comment
+    ///
+    /// Comment opening (`//`, or HTML-like `<!--` / `-->`) must already have been consumed.
     pub(super) fn skip_single_line_comment(&mut self) -> Kind {
+        let content_start = self.offset();
+        let mut comment_end = self.source.position();
         byte_search! {
             lexer: self,
             table: LINE_BREAK_TABLE,
@@ -36,7 +40,8 @@ impl<'a> Lexer<'a> {
                 if next_byte != LS_OR_PS_FIRST {
                     // `\r` or `\n`
                     self.trivia_builder
-                        .add_single_line_comment(self.token.start, self.source.offset_of(pos));
+                        .add_single_line_comment(content_start, self.source.offset_of(pos));
+                    comment_end = pos;
                     // SAFETY: Safe to consume `\r` or `\n` as both are ASCII
                     pos = unsafe { pos.add(1) };
                     // We've found the end. Do not continue searching.
@@ -51,7 +56,8 @@ impl<'a> Lexer<'a> {
                         if matches!(next2, LS_BYTES_2_AND_3 | PS_BYTES_2_AND_3) {
                             // Irregular line break
                             self.trivia_builder
-                                .add_single_line_comment(self.token.start, self.source.offset_of(pos));
+                                .add_single_line_comment(content_start, self.source.offset_of(pos));
+                            comment_end = pos;
                             // Advance `pos` to after this char.
                             // SAFETY: `0xE2` is always 1st byte of a 3-byte UTF-8 char,
                             // so consuming 3 bytes will place `pos` on next UTF-8 char boundary.
@@ -70,11 +76,17 @@ impl<'a> Lexer<'a> {
                 }
             },
             handle_eof: {
-                self.trivia_builder.add_single_line_comment(self.token.start, self.offset());
-                return Kind::Skip;
+                self.trivia_builder.add_single_line_comment(content_start, self.offset());
+                return self.comment_kind(Kind::LineComment);
             },
         };
 
+        if self.emit_comments {
+            // Leave line break to be lexed as start of next token
+            self.source.set_position(comment_end);
+            return Kind::LineComment;
+        }
+
         self.token.is_on_new_line = true;
         Kind::Skip
     }
@@ -151,7 +163,7 @@ impl<'a> Lexer<'a> {
         };
 
         self.trivia_builder.add_multi_line_comment(self.token.start, self.offset());
-        Kind::Skip
+        self.comment_kind(Kind::BlockComment)
     }
 
     fn skip_multi_line_comment_after_line_break(&mut self, pos: SourcePosition) -> Kind {
@@ -171,7 +183,7 @@ impl<'a> Lexer<'a> {
             // SAFETY: `pos + index + 2` is end of `*/`, so a valid `SourcePosition`
             self.source.set_position(unsafe { pos.add(index + 2) });
             self.trivia_builder.add_multi_line_comment(self.token.start, self.offset());
-            Kind::Skip
+            self.comment_kind(Kind::BlockComment)
         } else {
             self.source.advance_to_end();
             self.error(diagnostics::unterminated_multi_line_comment(self.unterminated_range()));
@@ -179,6 +191,16 @@ impl<'a> Lexer<'a> {
         }
     }
 
+    /// Get `Kind` for a comment. `Kind::Skip` unless emitting comments as tokens.
+    #[inline]
+    fn comment_kind(&self, kind: Kind) -> Kind {
+        if self.emit_comments {
+            kind
+        } else {
+            Kind::Skip
+        }
+    }
+
     /// Section 12.5 Hashbang Comments
     pub(super) fn read_hashbang_comment(&mut self) -> Kind {
         while let Some(c) = self.next_char().as_ref() {
@@ -190,3 +212,133 @@ impl<'a> Lexer<'a> {
         Kind::HashbangComment
     }
 }
+
+#[cfg(test)]
+mod test {
+    use oxc_allocator::Allocator;
+    use oxc_span::{SourceType, Span};
+
+    use crate::lexer::{
+        tester::{kinds, lex, lex_as, lex_to_end},
+        Kind, Lexer,
+    };
+
+    #[test]
+    fn hashbang() {
+        let allocator = Allocator::default();
+        let (tokens, lexer) = lex(&allocator, "#!/usr/bin/env node\nlet x");
+        assert_eq!(kinds(&tokens), [Kind::HashbangComment, Kind::Let, Kind::Ident]);
+        // Hashbang includes the line break which terminates it
+        assert_eq!(tokens[0].span(), Span::new(0, 20));
+        assert!(lexer.errors.is_empty());
+
+        // Hashbang with nothing after it
+        let (tokens, _) = lex(&allocator, "#!");
+        assert_eq!(kinds(&tokens), [Kind::HashbangComment]);
+
+        // Hashbang after BOM
+        let (tokens, _) = lex(&allocator, "\u{FEFF}#!/usr/bin/env node\nx");
+        assert_eq!(kinds(&tokens), [Kind::HashbangComment, Kind::Ident]);
+        assert_eq!(tokens[0].span(), Span::new(3, 23));
+
+        // `#!` anywhere else is not a hashbang
+        let (tokens, lexer) = lex(&allocator, "x #! y");
+        assert!(!kinds(&tokens).contains(&Kind::HashbangComment));
+        assert!(!lexer.errors.is_empty());
+
+        let (tokens, _) = lex(&allocator, " #!/usr/bin/env node");
+        assert!(!kinds(&tokens).contains(&Kind::HashbangComment));
+    }
+
+    #[test]
+    fn html_like_comments() {
+        let allocator = Allocator::default();
+        let lex_with_comments = |source, source_type| {
+            let (tokens, lexer) = lex_as(&allocator, source, source_type);
+            let comments = lexer.trivia().comments().map(|(span, _)| span).collect::<Vec<_>>();
+            (kinds(&tokens), comments)
+        };
+        let script = SourceType::default().with_module(false);
+        let module = SourceType::default().with_module(true);
+
+        // `<!--` is a comment in scripts only
+        let source = "x <!-- comment\ny";
+        assert_eq!(
+            lex_with_comments(source, script),
+            (vec![Kind::Ident, Kind::Ident], vec![Span::new(6, 14)])
+        );
+        assert_eq!(
+            lex_with_comments(source, module).0,
+            [Kind::Ident, Kind::LAngle, Kind::Bang, Kind::Minus2, Kind::Ident, Kind::Ident]
+        );
+
+        // `-->` is a comment in scripts only, and only at start of a line
+        let source = "x\n  --> comment\ny";
+        assert_eq!(
+            lex_with_comments(source, script),
+            (vec![Kind::Ident, Kind::Ident], vec![Span::new(7, 15)])
+        );
+        assert_eq!(
+            lex_with_comments(source, module).0,
+            [Kind::Ident, Kind::Minus2, Kind::RAngle, Kind::Ident, Kind::Ident]
+        );
+        let source = "x --> y";
+        let expected = (vec![Kind::Ident, Kind::Minus2, Kind::RAngle, Kind::Ident], vec![]);
+        assert_eq!(lex_with_comments(source, script), expected);
+        assert_eq!(lex_with_comments(source, module), expected);
+
+        // Multi-line comment containing a line break counts as start of line
+        let source = "x /*\n*/ --> comment";
+        assert_eq!(
+            lex_with_comments(source, script),
+            (vec![Kind::Ident], vec![Span::new(4, 5), Span::new(11, 19)])
+        );
+    }
+
+    #[test]
+    fn emit_comments() {
+        let allocator = Allocator::default();
+        let source = "a // line\nb /* block */ c /*\n*/ d <!-- html\ne // eof";
+
+        // Comments skipped by default
+        let (tokens, lexer) = lex(&allocator, source);
+        assert_eq!(kinds(&tokens), [Kind::Ident; 5]);
+        assert!(tokens[1].is_on_new_line);
+        assert!(tokens[3].is_on_new_line);
+        assert_eq!(lexer.trivia().comments().count(), 5);
+
+        // Comments emitted as tokens
+        let mut lexer = Lexer::new_for_tests(&allocator, source, SourceType::default());
+        lexer.set_emit_comments(true);
+        let tokens = lex_to_end(&mut lexer);
+        assert_eq!(
+            kinds(&tokens),
+            [
+                Kind::Ident,
+                Kind::LineComment,
+                Kind::Ident,
+                Kind::BlockComment,
+                Kind::Ident,
+                Kind::BlockComment,
+                Kind::Ident,
+                Kind::LineComment,
+                Kind::Ident,
+                Kind::LineComment,
+            ]
+        );
+        let texts = tokens.iter().map(|token| token.source_text(source)).collect::<Vec<_>>();
+        assert_eq!(
+            texts,
+            ["a", "// line", "b", "/* block */", "c", "/*\n*/", "d", "<!-- html", "e", "// eof"]
+        );
+        // Line break after single-line comment is attached to next token
+        assert!(!tokens[1].is_on_new_line);
+        assert!(tokens[2].is_on_new_line);
+        // Multi-line comment containing a line break is itself a line break
+        assert!(tokens[5].is_on_new_line);
+        assert_eq!(tokens.iter().filter(|token| !token.kind.is_trivia()).count(), 5);
+        // Comments still recorded in trivia
+        assert_eq!(lexer.trivia().comments().count(), 5);
+        assert!(lexer.errors.is_empty());
+    }
+}
@@ -49,6 +49,8 @@ impl<'a> Lexer<'a> {
     /// * Next char must be ASCII.
     #[allow(clippy::missing_safety_doc)] // Clippy is wrong!
     pub(super) unsafe fn identifier_name_handler(&mut self) -> &'a str {
+        debug_assert!(self.source.remaining_len() > 0);
+
         // Advance past 1st byte.
         // SAFETY: Caller guarantees not at EOF, and next byte is ASCII.
         let after_first = self.source.position().add(1);
@@ -131,14 +133,14 @@ impl<'a> Lexer<'a> {
     }
 
     /// Handle identifier starting with `\` escape.
-    pub fn identifier_backslash_handler(&mut self) -> Kind {
+    pub(super) fn identifier_backslash_handler(&mut self) -> Kind {
         // Create arena string to hold unescaped identifier.
         // We don't know how long identifier will end up being, so guess.
         let str = String::with_capacity_in(MIN_ESCAPED_STR_LEN, self.allocator);
 
         // Process escape and get rest of identifier
         let id = self.identifier_on_backslash(str, true);
-        Kind::match_keyword(id)
+        self.keyword_for_strict_mode(self.keyword_for_ecma_version(Kind::match_keyword(id)))
     }
 
     /// Consume rest of identifier after a `\` escape is found.
@@ -210,7 +212,7 @@ impl<'a> Lexer<'a> {
     /// Like `identifier_name_handler`, this contains a fast path for identifiers which are pure ASCII.
     /// Unicode characters and escapes are handled on paths marked `#[cold]` to keep the common ASCII
     /// fast path as fast as possible.
-    pub fn private_identifier(&mut self) -> Kind {
+    pub(super) fn private_identifier(&mut self) -> Kind {
         // Handle EOF directly after `#`
         let start_pos = self.source.position();
         if start_pos.addr() == self.source.end_addr() {
@@ -294,3 +296,64 @@ impl<'a> Lexer<'a> {
         Kind::Undetermined
     }
 }
+
+#[cfg(test)]
+mod test {
+    use oxc_allocator::Allocator;
+
+    use crate::lexer::{search::SEARCH_BATCH_SIZE, tester::lex, Kind};
+
+    #[test]
+    fn astral_identifiers() {
+        // `𝓍` (U+1D4CD) is `ID_Start`, 4 bytes in UTF-8
+        let allocator = Allocator::default();
+        for (source, names) in [
+            ("𝓍", &["𝓍"][..]),
+            ("𝓍ab", &["𝓍ab"]),
+            ("a𝓍b", &["a𝓍b"]),
+            ("ab𝓍", &["ab𝓍"]),
+            ("a𝓍𝓍 𝓍é𝓍", &["a𝓍𝓍", "𝓍é𝓍"]),
+            ("_𝓍$ = 𝓍", &["_𝓍$", "=", "𝓍"]),
+            ("a\\u{1D4CD}𝓍b", &["a\\u{1D4CD}𝓍b"]),
+        ] {
+            let (tokens, lexer) = lex(&allocator, source);
+            assert!(lexer.errors.is_empty(), "{source}");
+            let texts = tokens.iter().map(|token| token.source_text(source)).collect::<Vec<_>>();
+            assert_eq!(texts, names, "{source}");
+            // Tokens cover whole source, apart from whitespace
+            assert_eq!(tokens.last().unwrap().end as usize, source.len(), "{source}");
+        }
+
+        // Escaped astral char is decoded
+        let source = "a\\u{1D4CD}𝓍b";
+        let (tokens, lexer) = lex(&allocator, source);
+        assert_eq!(lexer.get_string(tokens[0]), "a𝓍𝓍b");
+
+        // Astral char which is not `ID_Continue` ends identifier
+        let source = "a𝓍\u{1F600}";
+        let (tokens, lexer) = lex(&allocator, source);
+        assert_eq!(tokens[0].kind, Kind::Ident);
+        assert_eq!(tokens[0].source_text(source), "a𝓍");
+        assert_eq!(lexer.errors.len(), 1);
+    }
+
+    #[test]
+    fn identifier_batch_boundary() {
+        let allocator = Allocator::default();
+        // Identifiers shorter than, exactly, and longer than a search batch,
+        // both at end of source and followed by another token
+        for len in 1..=SEARCH_BATCH_SIZE * 2 + 2 {
+            let name = "x".repeat(len);
+            for source in [name.clone(), format!("{name};"), format!("a {name}")] {
+                let (tokens, lexer) = lex(&allocator, &source);
+                let ident = tokens.iter().find(|token| {
+                    token.kind == Kind::Ident
+                        && source[token.start as usize..token.end as usize] == name
+                });
+                assert!(ident.is_some(), "{source}");
+                assert_eq!(tokens.last().unwrap().end as usize, source.len(), "{source}");
+                assert!(lexer.errors.is_empty());
+            }
+        }
+    }
+}
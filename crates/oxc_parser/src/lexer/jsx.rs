@@ -1,12 +1,15 @@
 use super::{
     cold_branch,
     search::{byte_search, safe_byte_match_table, SafeByteMatchTable},
+    source::SourcePosition,
     Kind, Lexer, Token,
 };
 use crate::diagnostics;
 
 use memchr::{memchr, memchr2};
-use oxc_syntax::identifier::is_identifier_part;
+use oxc_allocator::String;
+use oxc_span::Span;
+use oxc_syntax::{identifier::is_identifier_part, xml_entities::XML_ENTITIES};
 
 static NOT_ASCII_JSX_ID_CONTINUE_TABLE: SafeByteMatchTable =
     safe_byte_match_table!(|b| !(b.is_ascii_alphanumeric() || matches!(b, b'_' | b'$' | b'-')));
@@ -30,15 +33,14 @@ impl<'a> Lexer<'a> {
     pub(super) unsafe fn read_jsx_string_literal(&mut self, delimiter: u8) -> Kind {
         // Skip opening quote
         debug_assert!(delimiter.is_ascii());
-        // SAFETY: Caller guarantees next byte is ASCII, so `.add(1)` is a UTF-8 char boundary
-        let after_opening_quote = self.source.position().add(1);
-        let remaining = self.source.str_from_pos_to_end(after_opening_quote);
+        // SAFETY: Caller guarantees next byte is ASCII, so consuming it leaves `source`
+        // on a UTF-8 char boundary
+        self.source.next_byte_unchecked();
 
-        let len = memchr(delimiter, remaining.as_bytes());
-        if let Some(len) = len {
-            // SAFETY: `after_opening_quote` + `len` is position of delimiter.
+        if let Some(len) = self.source.find_byte(delimiter) {
+            // SAFETY: Current position + `len` is position of delimiter.
             // Caller guarantees delimiter is ASCII, so 1 byte after it is a UTF-8 char boundary.
-            let after_closing_quote = after_opening_quote.add(len + 1);
+            let after_closing_quote = self.source.position().add(len + 1);
             self.source.set_position(after_closing_quote);
             Kind::Str
         } else {
@@ -71,6 +73,7 @@ impl<'a> Lexer<'a> {
                 Kind::LCurly
             }
             Some(_) => {
+                let start = self.source.position();
                 // The tokens `{`, `<`, `>` and `}` cannot appear in JSX text.
                 // The TypeScript compiler raises the error "Unexpected token. Did you mean `{'>'}` or `&gt;`?".
                 // Where as the Babel compiler does not raise any errors.
@@ -84,12 +87,64 @@ impl<'a> Lexer<'a> {
                 } else {
                     self.source.advance_to_end();
                 }
+                if self.decode_jsx_entities {
+                    self.decode_jsx_text_entities(start);
+                }
                 Kind::JSXText
             }
             None => Kind::Eof,
         }
     }
 
+    /// Decode HTML character references in JSX text from `start` to current position.
+    /// If any are found, decoded text is saved in `escaped_strings`.
+    ///
+    /// A character reference is `&` followed by a name, `#` and a decimal number,
+    /// or `#x` and a hex number, and terminated by `;`.
+    /// Any other `&` is just text.
+    fn decode_jsx_text_entities(&mut self, start: SourcePosition<'a>) {
+        let text = self.source.str_from_pos_to_current(start);
+        if memchr(b'&', text.as_bytes()).is_none() {
+            return;
+        }
+
+        cold_branch(|| {
+            let start_offset = self.source.offset_of(start);
+            let mut decoded = String::with_capacity_in(text.len(), self.allocator);
+            let mut has_entity = false;
+            let mut last_end = 0;
+            for (index, _) in text.match_indices('&') {
+                let rest = &text[index + 1..];
+                let Some(len) =
+                    rest.bytes().position(|b| !(b.is_ascii_alphanumeric() || b == b'#'))
+                else {
+                    break;
+                };
+                if len == 0 || rest.as_bytes()[len] != b';' {
+                    continue;
+                }
+
+                let name = &rest[..len];
+                let end = index + len + 2;
+                if let Some(c) = decode_jsx_entity(name) {
+                    decoded.push_str(&text[last_end..index]);
+                    decoded.push(c);
+                    last_end = end;
+                    has_entity = true;
+                } else {
+                    #[allow(clippy::cast_possible_truncation)]
+                    let span = Span::new(start_offset + index as u32, start_offset + end as u32);
+                    self.error(diagnostics::invalid_jsx_entity(name, span));
+                }
+            }
+
+            if has_entity {
+                decoded.push_str(&text[last_end..]);
+                self.save_string(true, decoded.into_bump_str());
+            }
+        });
+    }
+
     /// Expand the current `Ident` token for `JSXIdentifier`
     ///
     /// The current character is at `Ident`, continue reading for `JSXIdentifier` if it has a `-`
@@ -135,3 +190,101 @@ impl<'a> Lexer<'a> {
         Some(self.finish_next(Kind::Ident))
     }
 }
+
+/// Get char which HTML character reference `&name;` represents, or `None` if invalid.
+fn decode_jsx_entity(name: &str) -> Option<char> {
+    if let Some(number) = name.strip_prefix('#') {
+        let code_point = match number.strip_prefix(['x', 'X']) {
+            Some(hex) => u32::from_str_radix(hex, 16),
+            None => number.parse::<u32>(),
+        };
+        code_point.ok().and_then(char::from_u32)
+    } else {
+        XML_ENTITIES.get(name).copied()
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use oxc_allocator::Allocator;
+    use oxc_span::{SourceType, Span};
+
+    use crate::lexer::{tester::error_spans, Kind, Lexer, LexerContext};
+
+    #[test]
+    fn jsx_entities() {
+        let allocator = Allocator::default();
+        let source_type = SourceType::default().with_jsx(true);
+        let lex_jsx_text = |source: &'static str, decode: bool| {
+            let mut lexer = Lexer::new_for_tests(&allocator, source, source_type);
+            lexer.set_decode_jsx_entities(decode);
+            let token = lexer.next_jsx_child();
+            assert_eq!(token.kind, Kind::JSXText, "{source}");
+            let value = lexer.get_string(token);
+            (token, value, error_spans(&lexer))
+        };
+
+        for (source, value) in [
+            ("a &amp; b", "a & b"),
+            ("&#38;", "&"),
+            ("&#x26;&#X3c;", "&<"),
+            ("&lt;div&gt; &nbsp;&copy;", "<div> \u{a0}©"),
+            ("&#x1F600;!", "\u{1F600}!"),
+            // Not character references
+            ("a & b; &amp &#38 &; &&", "a & b; &amp &#38 &; &&"),
+            ("ü&", "ü&"),
+        ] {
+            let (token, decoded, errors) = lex_jsx_text(source, true);
+            assert_eq!(decoded, value, "{source}");
+            assert_eq!(token.escaped, source != value, "{source}");
+            assert_eq!(token.end as usize, source.len(), "{source}");
+            assert!(errors.is_empty(), "{source}");
+
+            // Not decoded by default
+            let (token, raw, errors) = lex_jsx_text(source, false);
+            assert_eq!(raw, source);
+            assert!(!token.escaped);
+            assert!(errors.is_empty());
+        }
+
+        // Invalid references are reported, and left as is
+        let (token, decoded, errors) =
+            lex_jsx_text("&foo; &amp; &#xD800; &#x; &#99999999999;", true);
+        assert_eq!(decoded, "&foo; & &#xD800; &#x; &#99999999999;");
+        assert_eq!(token.span(), Span::new(0, 40));
+        assert_eq!(
+            errors,
+            [Span::new(0, 5), Span::new(12, 20), Span::new(21, 25), Span::new(26, 40)]
+        );
+
+        // Text is terminated by `{` and `<`, but not `}` or `>`
+        let source = "&amp;} > &lt;{x}&gt;<";
+        let mut lexer = Lexer::new_for_tests(&allocator, source, source_type);
+        lexer.set_decode_jsx_entities(true);
+        let token = lexer.next_jsx_child();
+        assert_eq!((token.kind, token.span()), (Kind::JSXText, Span::new(0, 13)));
+        assert_eq!(lexer.get_string(token), "&} > <");
+        assert_eq!(lexer.next_jsx_child().kind, Kind::LCurly);
+        assert_eq!(lexer.next_token().kind, Kind::Ident);
+        assert_eq!(lexer.next_token().kind, Kind::RCurly);
+        let token = lexer.next_jsx_child();
+        assert_eq!((token.kind, token.span()), (Kind::JSXText, Span::new(16, 20)));
+        assert_eq!(lexer.get_string(token), ">");
+        assert_eq!(lexer.next_jsx_child().kind, Kind::LAngle);
+        assert!(lexer.errors.is_empty());
+    }
+
+    #[test]
+    fn jsx_attribute_string() {
+        let allocator = Allocator::default();
+        let source_type = SourceType::default().with_jsx(true);
+        for (source, end, errors) in [("'a\\'", 4, 0), ("\"é\"\" x", 4, 0), ("'abc", 4, 1)] {
+            let mut lexer = Lexer::new_for_tests(&allocator, source, source_type);
+            lexer.set_context(LexerContext::JsxAttributeValue);
+            let token = lexer.next_token();
+            assert_eq!(token.kind, if errors == 0 { Kind::Str } else { Kind::Undetermined });
+            assert_eq!(token.end, end, "{source}");
+            assert_eq!(lexer.errors.len(), errors, "{source}");
+        }
+    }
+}
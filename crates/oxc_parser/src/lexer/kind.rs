@@ -2,13 +2,16 @@
 
 use std::fmt;
 
-#[derive(Debug, Default, Clone, Copy, Eq, PartialEq)]
+#[derive(Debug, Default, Clone, Copy, Eq, PartialEq, Hash)]
 #[non_exhaustive]
 pub enum Kind {
     Undetermined,
     #[default]
     Eof,
     Skip, // Whitespace, line breaks, comments
+    // 12.4 Comments. Only produced when lexer is emitting comments as tokens.
+    LineComment,
+    BlockComment,
     // 12.5 Hashbang Comments
     HashbangComment,
     // 12.7.1 identifier
@@ -199,11 +202,22 @@ impl Kind {
         matches!(self, Eof)
     }
 
-    pub fn is_number(self) -> bool {
-        matches!(
-            self,
-            Float | Decimal | Binary | Octal | Hex | PositiveExponential | NegativeExponential
-        )
+    /// Whitespace, line breaks and comments.
+    ///
+    /// `HashbangComment` is not included, as it's part of the AST.
+    pub fn is_trivia(self) -> bool {
+        matches!(self, Skip | LineComment | BlockComment)
+    }
+
+    pub const fn is_number(self) -> bool {
+        self.is_in_range(Decimal, NegativeExponential)
+    }
+
+    /// Check if `self` is between `first` and `last` (inclusive) in order of definition of `Kind`.
+    /// Only valid where all kinds in the range form a single category.
+    #[inline]
+    const fn is_in_range(self, first: Self, last: Self) -> bool {
+        first as u8 <= self as u8 && self as u8 <= last as u8
     }
 
     pub fn matches_number_char(self, c: char) -> bool {
@@ -240,13 +254,13 @@ impl Kind {
 
     /// `IdentifierName`
     pub fn is_identifier_name(self) -> bool {
-        matches!(self, Ident) || self.is_all_keyword()
+        matches!(self, Ident) || self.is_keyword()
     }
 
     /// Check the succeeding token of a `let` keyword
     // let { a, b } = c, let [a, b] = c, let ident
     pub fn is_after_let(self) -> bool {
-        self != Self::In && (matches!(self, LCurly | LBrack | Ident) || self.is_all_keyword())
+        self != Self::In && (matches!(self, LCurly | LBrack | Ident) || self.is_keyword())
     }
 
     /// Section 13.2.4 Literals
@@ -255,8 +269,9 @@ impl Kind {
     ///     `BooleanLiteral`
     ///     `NumericLiteral`
     ///     `StringLiteral`
-    pub fn is_literal(self) -> bool {
-        matches!(self, Null | True | False | Str | RegExp) || self.is_number()
+    ///     `RegularExpressionLiteral`
+    pub const fn is_literal(self) -> bool {
+        self.is_in_range(Null, RegExp)
     }
 
     pub fn is_after_await_or_yield(self) -> bool {
@@ -288,14 +303,14 @@ impl Kind {
     }
 
     #[rustfmt::skip]
-    pub fn is_assignment_operator(self) -> bool {
+    pub const fn is_assignment_operator(self) -> bool {
         matches!(self, Eq | PlusEq | MinusEq | StarEq | SlashEq | PercentEq | ShiftLeftEq | ShiftRightEq
             | ShiftRight3Eq | Pipe2Eq | Amp2Eq | PipeEq | CaretEq | AmpEq | Question2Eq
             | Star2Eq)
     }
 
     #[rustfmt::skip]
-    pub fn is_binary_operator(self) -> bool {
+    pub const fn is_binary_operator(self) -> bool {
         matches!(self, Eq2 | Neq | Eq3 | Neq2 | LAngle | LtEq | RAngle | GtEq | ShiftLeft | ShiftRight
             | ShiftRight3 | Plus | Minus | Star | Slash | Percent | Pipe | Caret | Amp | In
             | Instanceof | Star2)
@@ -305,6 +320,39 @@ impl Kind {
         matches!(self, Pipe2 | Amp2 | Question2)
     }
 
+    /// Get left and right binding powers of a binary, logical or assignment operator,
+    /// for Pratt parsing. Returns `None` for any other `Kind`.
+    ///
+    /// Tiers follow ECMAScript operator precedence, lowest to highest: assignment, `??`, `||`, `&&`,
+    /// `|`, `^`, `&`, equality, relational, shift, additive, multiplicative, exponentiation.
+    /// Left-associative operators bind more tightly on the right, right-associative operators
+    /// (assignment and `**`) more tightly on the left.
+    ///
+    /// `??` has a tier of its own, below `||`. Mixing `??` with `||` or `&&` without parentheses
+    /// is a syntax error, which binding powers cannot express, so that is left to the parser.
+    ///
+    /// TypeScript's `as` and `satisfies` are not included.
+    pub const fn binding_power(self) -> Option<(u8, u8)> {
+        let left = match self {
+            Question2 => 3,
+            Pipe2 => 5,
+            Amp2 => 7,
+            Pipe => 9,
+            Caret => 11,
+            Amp => 13,
+            Eq2 | Eq3 | Neq | Neq2 => 15,
+            LAngle | RAngle | LtEq | GtEq | Instanceof | In => 17,
+            ShiftLeft | ShiftRight | ShiftRight3 => 19,
+            Plus | Minus => 21,
+            Star | Slash | Percent => 23,
+            // Right-associative
+            Star2 => return Some((26, 25)),
+            _ if self.is_assignment_operator() => return Some((2, 1)),
+            _ => return None,
+        };
+        Some((left, left + 1))
+    }
+
     pub fn is_unary_operator(self) -> bool {
         matches!(self, Minus | Plus | Bang | Tilde | Typeof | Void | Delete)
     }
@@ -314,19 +362,15 @@ impl Kind {
     }
 
     /// [Keywords and Reserved Words](https://tc39.es/ecma262/#sec-keywords-and-reserved-words)
-    pub fn is_all_keyword(self) -> bool {
-        self.is_reserved_keyword()
-            || self.is_contextual_keyword()
-            || self.is_strict_mode_contextual_keyword()
-            || self.is_future_reserved_keyword()
+    ///
+    /// All reserved, contextual, strict mode contextual and future reserved keywords.
+    pub const fn is_keyword(self) -> bool {
+        // Keywords are defined contiguously, except for `null`, `true` and `false`, which are literals
+        self.is_in_range(Await, Yield) || matches!(self, Null | True | False)
     }
 
-    #[rustfmt::skip]
-    pub fn is_reserved_keyword(self) -> bool {
-        matches!(self, Await | Break | Case | Catch | Class | Const | Continue | Debugger | Default
-            | Delete | Do | Else | Enum | Export | Extends | False | Finally | For | Function | If
-            | Import | In | Instanceof | New | Null | Return | Super | Switch | This | Throw
-            | True | Try | Typeof | Var | Void | While | With | Yield)
+    pub const fn is_reserved_keyword(self) -> bool {
+        self.is_in_range(Await, With) || matches!(self, Null | True | False | Yield)
     }
 
     #[rustfmt::skip]
@@ -475,11 +519,15 @@ impl Kind {
         }
     }
 
-    pub fn to_str(self) -> &'static str {
+    /// Get canonical spelling of punctuators and keywords (e.g. `=>`, `var`),
+    /// or a descriptive name for other kinds of token (e.g. `string literal`).
+    pub const fn as_str(self) -> &'static str {
         match self {
             Undetermined => "Unknown",
             Eof => "EOF",
             Skip => "Skipped",
+            LineComment => "//",
+            BlockComment => "/* */",
             HashbangComment => "#!",
             Ident => "Identifier",
             Await => "await",
@@ -606,7 +654,8 @@ impl Kind {
             Binary => "binary",
             Octal => "octal",
             Hex => "hex",
-            Str | String => "string",
+            Str => "string literal",
+            String => "string",
             RegExp => "/regexp/",
             NoSubstitutionTemplate => "${}",
             TemplateHead => "${",
@@ -624,7 +673,7 @@ impl Kind {
             Is => "is",
             KeyOf => "keyof",
             Module => "module",
-            Namespace => "namaespace",
+            Namespace => "namespace",
             Never => "never",
             Out => "out",
             Require => "require",
@@ -640,6 +689,21 @@ impl Kind {
         }
     }
 
+    /// Length in bytes of source text of a token of this kind, if all tokens of this kind
+    /// have same length (e.g. `Some(1)` for `+`, `Some(2)` for `=>`, `Some(3)` for `**=`).
+    ///
+    /// Returns `None` for variable-length tokens (identifiers, strings, numbers etc).
+    /// Keywords also return `None`, as they can be written with unicode escapes (`\u0076ar`).
+    #[allow(clippy::cast_possible_truncation)]
+    pub const fn fixed_len(self) -> Option<u32> {
+        // Punctuators are defined contiguously from `Amp` to `Tilde`, followed by `Arrow`
+        if self.is_in_range(Amp, Arrow) || matches!(self, At) {
+            Some(self.as_str().len() as u32)
+        } else {
+            None
+        }
+    }
+
     #[rustfmt::skip]
     pub fn can_follow_type_arguments_in_expr(self) -> bool {
         matches!(self, Self::LParen | Self::NoSubstitutionTemplate | Self::TemplateHead
@@ -652,6 +716,247 @@ impl Kind {
 
 impl fmt::Display for Kind {
     fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
-        write!(f, "{}", self.to_str())
+        f.write_str(self.as_str())
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use oxc_allocator::Allocator;
+
+    use super::Kind::{self, *};
+    use crate::lexer::tester::{kinds, lex};
+
+    // Classification methods can be used in const context
+    const _: () = {
+        assert!(Eq.is_assignment_operator());
+        assert!(Plus.is_binary_operator());
+        assert!(Await.is_keyword());
+        assert!(While.is_reserved_keyword());
+        assert!(Str.is_literal());
+        assert!(matches!(Star2.binding_power(), Some((26, 25))));
+    };
+
+    #[test]
+    fn keywords() {
+        let reserved = [
+            Await, Break, Case, Catch, Class, Const, Continue, Debugger, Default, Delete, Do, Else,
+            Enum, Export, Extends, False, Finally, For, Function, If, Import, In, Instanceof, New,
+            Null, Return, Super, Switch, This, Throw, True, Try, Typeof, Var, Void, While, With,
+            Yield,
+        ];
+        for kind in reserved {
+            assert!(kind.is_reserved_keyword(), "{kind:?}");
+            assert!(kind.is_keyword(), "{kind:?}");
+        }
+
+        for kind in [Async, Of, Accessor, Abstract, Override, Let, Static, Implements, Public] {
+            assert!(!kind.is_reserved_keyword(), "{kind:?}");
+            assert!(kind.is_keyword(), "{kind:?}");
+        }
+
+        for kind in [Undetermined, Eof, Skip, HashbangComment, Ident, Amp, Decimal, Str, At] {
+            assert!(!kind.is_reserved_keyword(), "{kind:?}");
+            assert!(!kind.is_keyword(), "{kind:?}");
+        }
+    }
+
+    #[test]
+    fn trivia() {
+        for kind in [Skip, LineComment, BlockComment] {
+            assert!(kind.is_trivia(), "{kind:?}");
+        }
+        for kind in [Undetermined, Eof, HashbangComment, Ident, Slash, RegExp, Str] {
+            assert!(!kind.is_trivia(), "{kind:?}");
+        }
+    }
+
+    #[test]
+    fn operators() {
+        for kind in [Eq, PlusEq, ShiftRight3Eq, Amp2Eq, Question2Eq, Star2Eq] {
+            assert!(kind.is_assignment_operator(), "{kind:?}");
+            assert!(!kind.is_binary_operator(), "{kind:?}");
+        }
+        for kind in [Plus, Eq3, Neq2, LAngle, ShiftRight3, Star2, In, Instanceof] {
+            assert!(kind.is_binary_operator(), "{kind:?}");
+            assert!(!kind.is_assignment_operator(), "{kind:?}");
+        }
+        for kind in [Amp2, Pipe2, Question2, Bang, Plus2, Arrow, Dot, Ident] {
+            assert!(!kind.is_assignment_operator(), "{kind:?}");
+            assert!(!kind.is_binary_operator(), "{kind:?}");
+        }
+    }
+
+    #[test]
+    fn literals() {
+        let numbers =
+            [Decimal, Float, Binary, Octal, Hex, PositiveExponential, NegativeExponential];
+        for kind in numbers {
+            assert!(kind.is_number(), "{kind:?}");
+            assert!(kind.is_literal(), "{kind:?}");
+        }
+        for kind in [Null, True, False, Str, RegExp] {
+            assert!(!kind.is_number(), "{kind:?}");
+            assert!(kind.is_literal(), "{kind:?}");
+        }
+        for kind in [Arrow, NoSubstitutionTemplate, TemplateHead, Ident, Undefined, Eof] {
+            assert!(!kind.is_number(), "{kind:?}");
+            assert!(!kind.is_literal(), "{kind:?}");
+        }
+    }
+
+    #[test]
+    fn keyword_categories_agree() {
+        // `is_keyword` is implemented with a range check, so check it agrees with the
+        // individual keyword categories
+        for s in ["await", "yield", "let", "static", "of", "async", "override", "x", "null"] {
+            let kind = Kind::match_keyword(s);
+            assert_eq!(
+                kind.is_keyword(),
+                kind.is_reserved_keyword()
+                    || kind.is_contextual_keyword()
+                    || kind.is_strict_mode_contextual_keyword()
+                    || kind.is_future_reserved_keyword(),
+                "{kind:?}"
+            );
+        }
+    }
+
+    #[test]
+    fn binding_power() {
+        let bp = |kind: Kind| kind.binding_power().unwrap();
+
+        // `**` is right-associative
+        assert!(bp(Star2).1 < bp(Star2).0);
+        assert!(bp(Eq).1 < bp(Eq).0);
+        assert!(bp(Star2Eq).1 < bp(Star2Eq).0);
+        // Others are left-associative
+        for kind in [Question2, Pipe2, Amp2, Pipe, Caret, Amp, Eq3, In, ShiftRight3, Minus, Percent]
+        {
+            assert!(bp(kind).0 < bp(kind).1, "{kind:?}");
+        }
+
+        // Precedence tiers, lowest first
+        let tiers = [
+            &[Eq, PlusEq, Question2Eq][..],
+            &[Question2],
+            &[Pipe2],
+            &[Amp2],
+            &[Pipe],
+            &[Caret],
+            &[Amp],
+            &[Eq2, Eq3, Neq, Neq2],
+            &[LAngle, RAngle, LtEq, GtEq, Instanceof, In],
+            &[ShiftLeft, ShiftRight, ShiftRight3],
+            &[Plus, Minus],
+            &[Star, Slash, Percent],
+            &[Star2],
+        ];
+        for pair in tiers.windows(2) {
+            for &lower in pair[0] {
+                assert_eq!(bp(lower), bp(pair[0][0]), "{lower:?}");
+                for &higher in pair[1] {
+                    assert!(bp(lower).1 < bp(higher).0, "{lower:?} {higher:?}");
+                }
+            }
+        }
+
+        // `*` binds tighter than `+`
+        assert!(bp(Star).0 > bp(Plus).1);
+        // `??` is in a different tier from `||`
+        assert_ne!(bp(Question2), bp(Pipe2));
+
+        for kind in [Bang, Tilde, Plus2, Question, Arrow, Comma, Dot, Ident, As, Satisfies, Eof] {
+            assert_eq!(kind.binding_power(), None, "{kind:?}");
+        }
+    }
+
+    #[test]
+    fn as_str() {
+        assert_eq!(Arrow.as_str(), "=>");
+        assert_eq!(Var.as_str(), "var");
+        assert_eq!(Str.as_str(), "string literal");
+        assert_eq!(String.as_str(), "string");
+        assert_eq!(ShiftRight3Eq.to_string(), ">>>=");
+
+        #[rustfmt::skip]
+        let all = [
+            Undetermined, Eof, Skip, LineComment, BlockComment, HashbangComment, Ident, Await,
+            Break, Case, Catch, Class, Const, Continue, Debugger, Default, Delete, Do, Else, Enum,
+            Export, Extends, Finally, For, Function, If, Import, In, Instanceof, New, Return, Super,
+            Switch, This, Throw, Try, Typeof, Var, Void, While, With, Async, From, Get, Meta, Of,
+            Set, Target, Accessor, Abstract, As, Asserts, Assert, Any, Boolean, Constructor,
+            Declare, Infer, Intrinsic, Is, KeyOf, Module, Namespace, Never, Out, Readonly, Require,
+            Number, Object, Satisfies, String, Symbol, Type, Undefined, Unique, Using, Unknown,
+            Global, BigInt, Override, Implements, Interface, Let, Package, Private, Protected,
+            Public, Static, Yield, Amp, Amp2, Amp2Eq, AmpEq, Bang, Caret, CaretEq, Colon, Comma,
+            Dot, Dot3, Eq, Eq2, Eq3, GtEq, LAngle, LBrack, LCurly, LParen, LtEq, Minus, Minus2,
+            MinusEq, Neq, Neq2, Percent, PercentEq, Pipe, Pipe2, Pipe2Eq, PipeEq, Plus, Plus2,
+            PlusEq, Question, Question2, Question2Eq, QuestionDot, RAngle, RBrack, RCurly, RParen,
+            Semicolon, ShiftLeft, ShiftLeftEq, ShiftRight, ShiftRight3, ShiftRight3Eq, ShiftRightEq,
+            Slash, SlashEq, Star, Star2, Star2Eq, StarEq, Tilde, Arrow, Null, True, False, Decimal,
+            Float, Binary, Octal, Hex, PositiveExponential, NegativeExponential, Str, RegExp,
+            NoSubstitutionTemplate, TemplateHead, TemplateMiddle, TemplateTail, PrivateIdentifier,
+            JSXText, At,
+        ];
+        // `At` is the last variant of `Kind`, so this fails if a variant is missing from list
+        assert_eq!(all.len(), At as usize + 1);
+        for kind in all {
+            assert!(!kind.as_str().is_empty(), "{kind:?}");
+        }
+    }
+
+    #[test]
+    fn fixed_len() {
+        for (kind, len) in [
+            (Plus, 1),
+            (Comma, 1),
+            (At, 1),
+            (Arrow, 2),
+            (QuestionDot, 2),
+            (Star2Eq, 3),
+            (Dot3, 3),
+            (ShiftRight3Eq, 4),
+        ] {
+            assert_eq!(kind.fixed_len(), Some(len), "{kind:?}");
+        }
+
+        for kind in [Ident, Str, Decimal, Float, RegExp, TemplateHead, PrivateIdentifier, Var, Eof]
+        {
+            assert_eq!(kind.fixed_len(), None, "{kind:?}");
+        }
+    }
+
+    #[test]
+    fn using() {
+        let allocator = Allocator::default();
+
+        // `using` is a contextual keyword. Parser decides if it's a declaration or an identifier.
+        for (source, expected) in [
+            (
+                "using x = foo()",
+                &[Kind::Using, Kind::Ident, Kind::Eq, Kind::Ident, Kind::LParen, Kind::RParen][..],
+            ),
+            (
+                "await using y = bar()",
+                &[
+                    Kind::Await,
+                    Kind::Using,
+                    Kind::Ident,
+                    Kind::Eq,
+                    Kind::Ident,
+                    Kind::LParen,
+                    Kind::RParen,
+                ],
+            ),
+            ("let using = 1", &[Kind::Let, Kind::Using, Kind::Eq, Kind::Decimal]),
+        ] {
+            let (tokens, lexer) = lex(&allocator, source);
+            assert_eq!(kinds(&tokens), expected, "{source}");
+            assert!(lexer.errors.is_empty());
+        }
+        assert!(Kind::Using.is_contextual_keyword());
+        assert!(!Kind::Using.is_reserved_keyword());
+        assert!(Kind::Using.is_binding_identifier());
     }
 }
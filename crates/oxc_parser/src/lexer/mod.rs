@@ -21,6 +21,8 @@ mod search;
 mod source;
 mod string;
 mod template;
+#[cfg(test)]
+mod tester;
 mod token;
 mod trivia_builder;
 mod typescript;
@@ -28,24 +30,27 @@ mod unicode;
 mod whitespace;
 
 use rustc_hash::FxHashMap;
-use std::collections::VecDeque;
+use std::{collections::VecDeque, iter::FusedIterator};
 
 use oxc_allocator::Allocator;
-use oxc_ast::ast::RegExpFlags;
+use oxc_ast::{ast::RegExpFlags, Trivias};
 use oxc_diagnostics::OxcDiagnostic;
 use oxc_span::{SourceType, Span};
+use oxc_syntax::identifier::{
+    is_identifier_start_unicode, is_irregular_line_terminator, is_irregular_whitespace,
+};
 
 use self::{
     byte_handlers::handle_byte,
     source::{Source, SourcePosition},
-    trivia_builder::TriviaBuilder,
 };
 pub use self::{
     kind::Kind,
     number::{parse_big_int, parse_float, parse_int},
     token::Token,
+    trivia_builder::TriviaBuilder,
 };
-use crate::{diagnostics, UniquePromise};
+use crate::{diagnostics, EcmaVersion, UniquePromise};
 
 #[derive(Debug, Clone, Copy)]
 pub struct LexerCheckpoint<'a> {
@@ -54,9 +59,48 @@ pub struct LexerCheckpoint<'a> {
 
     token: Token,
 
+    current_token: Token,
+
     errors_pos: usize,
+
+    strict: bool,
+}
+
+/// Return value of [`Lexer::finish`], consisting of data collected while lexing.
+pub struct LexerReturn<'a> {
+    pub errors: Vec<OxcDiagnostic>,
+    pub trivias: Trivias,
+    /// Escaped strings, indexed by [Token::start]
+    pub escaped_strings: FxHashMap<u32, &'a str>,
+    /// Escaped templates, indexed by [Token::start].
+    /// `None` for templates containing an invalid escape sequence.
+    pub escaped_templates: FxHashMap<u32, Option<&'a str>>,
+}
+
+/// Iterator over tokens of a [`Lexer`]. Created by [`Lexer::tokens`].
+///
+/// Yields the `Eof` token last, and then `None` forever after.
+pub struct TokenStream<'l, 'a> {
+    lexer: &'l mut Lexer<'a>,
+    done: bool,
+}
+
+impl<'l, 'a> Iterator for TokenStream<'l, 'a> {
+    type Item = Token;
+
+    #[inline]
+    fn next(&mut self) -> Option<Token> {
+        if self.done {
+            return None;
+        }
+        let token = self.lexer.next_token();
+        self.done = token.kind == Kind::Eof;
+        Some(token)
+    }
 }
 
+impl<'l, 'a> FusedIterator for TokenStream<'l, 'a> {}
+
 #[derive(Debug, Copy, Clone, Eq, PartialEq)]
 pub enum LexerContext {
     Regular,
@@ -78,8 +122,12 @@ pub struct Lexer<'a> {
 
     source_type: SourceType,
 
+    /// Token currently being lexed
     token: Token,
 
+    /// Token most recently returned by the lexer
+    current_token: Token,
+
     pub(crate) errors: Vec<OxcDiagnostic>,
 
     lookahead: VecDeque<Lookahead<'a>>,
@@ -95,23 +143,43 @@ pub struct Lexer<'a> {
     /// `None` is saved when the string contains an invalid escape sequence.
     pub escaped_templates: FxHashMap<u32, Option<&'a str>>,
 
+    /// If `true`, comments are returned as `LineComment` / `BlockComment` tokens,
+    /// instead of being skipped. Either way, they are recorded in trivia.
+    emit_comments: bool,
+
+    /// If `true`, HTML entities in JSX text are decoded.
+    decode_jsx_entities: bool,
+
+    /// If `true`, source is lexed as strict mode code.
+    /// Initialized from `source_type`, but can be changed with [`Lexer::set_strict`].
+    strict: bool,
+
+    /// ECMAScript version. Determines which words are lexed as keywords.
+    ecma_version: EcmaVersion,
+
     /// `memchr` Finder for end of multi-line comments. Created lazily when first used.
     multi_line_comment_end_finder: Option<memchr::memmem::Finder<'static>>,
 }
 
 #[allow(clippy::unused_self)]
 impl<'a> Lexer<'a> {
-    /// Create new `Lexer`.
+    /// Create new `Lexer`, with all offsets shifted by `base_offset` (usually 0).
+    ///
+    /// A base offset is for lexing a snippet extracted from a larger document (e.g. an HTML `<script>`
+    /// tag), so that spans of tokens, trivia and diagnostics refer to positions in the original document.
+    /// If end of source would be at an offset greater than `u32::MAX`, source text is substituted
+    /// the same as for source exceeding `MAX_LEN`.
     ///
     /// Requiring a `UniquePromise` to be provided guarantees only 1 `Lexer` can exist
     /// on a single thread at one time.
-    pub(super) fn new(
+    pub(super) fn new_with_offset(
         allocator: &'a Allocator,
         source_text: &'a str,
         source_type: SourceType,
+        base_offset: u32,
         unique: UniquePromise,
     ) -> Self {
-        let source = Source::new(source_text, unique);
+        let source = Source::new(source_text, base_offset, unique);
 
         // The first token is at the start of file, so is allows on a new line
         let token = Token::new_on_new_line();
@@ -120,16 +188,33 @@ impl<'a> Lexer<'a> {
             source,
             source_type,
             token,
+            current_token: Token::default(),
             errors: vec![],
             lookahead: VecDeque::with_capacity(4), // 4 is the maximum lookahead for TypeScript
             context: LexerContext::Regular,
             trivia_builder: TriviaBuilder::default(),
             escaped_strings: FxHashMap::default(),
             escaped_templates: FxHashMap::default(),
+            emit_comments: false,
+            decode_jsx_entities: false,
+            strict: source_type.is_strict(),
+            ecma_version: EcmaVersion::Latest,
             multi_line_comment_end_finder: None,
         }
     }
 
+    /// Backdoor to create a `Lexer` without holding a `UniquePromise`, for unit tests.
+    /// This function must NOT be exposed in public API as it breaks safety invariants.
+    #[cfg(test)]
+    fn new_for_tests(
+        allocator: &'a Allocator,
+        source_text: &'a str,
+        source_type: SourceType,
+    ) -> Self {
+        let unique = UniquePromise::new_for_tests();
+        Self::new_with_offset(allocator, source_text, source_type, 0, unique)
+    }
+
     /// Backdoor to create a `Lexer` without holding a `UniquePromise`, for benchmarks.
     /// This function must NOT be exposed in public API as it breaks safety invariants.
     #[cfg(feature = "benchmarking")]
@@ -139,7 +224,196 @@ impl<'a> Lexer<'a> {
         source_type: SourceType,
     ) -> Self {
         let unique = UniquePromise::new_for_tests();
-        Self::new(allocator, source_text, source_type, unique)
+        Self::new_with_offset(allocator, source_text, source_type, 0, unique)
+    }
+
+    /// Create a `Lexer` to be driven standalone, without a parser, on arbitrary input.
+    ///
+    /// Lexing never panics, whatever the input. All problems are reported as diagnostics,
+    /// including source text exceeding `MAX_LEN`, which `Parser` otherwise reports.
+    ///
+    /// # Release-mode invariants
+    ///
+    /// All unsafe code in the lexer reads source text through `Source`, whose invariants hold for
+    /// any valid UTF-8 input. `debug_assert!`s only check those invariants, so nothing relies on them
+    /// for soundness in release builds:
+    ///
+    /// * Source exceeding `MAX_LEN` is replaced by `"\0"` before any pointers are created,
+    ///   so all offsets fit in a `u32`. It lexes as an invalid character.
+    /// * Unchecked reads happen only after checking for EOF, or in `byte_search!` batches,
+    ///   which only run when at least a full batch of bytes remains.
+    /// * Unterminated strings, templates and comments end at EOF, and are reported as errors.
+    ///   Invalid escapes are reported as errors, and lexing continues after them.
+    /// * The lexer keeps no state for nesting of templates (the parser drives continuation of
+    ///   a template after `}`), so deeply nested templates cannot exhaust the stack or memory.
+    /// * A `SourcePosition` can only be passed into the lexer in a `LexerCheckpoint`,
+    ///   and `Lexer::rewind` checks it's a valid position in this `Lexer`'s source.
+    ///
+    /// Every token other than `Eof` consumes at least 1 byte, so lexing always terminates.
+    ///
+    /// Like `new_for_benchmarks`, this does not require a `UniquePromise`, so must NOT be exposed
+    /// in public API.
+    #[cfg(any(test, feature = "benchmarking"))]
+    pub fn new_recovering(
+        allocator: &'a Allocator,
+        source_text: &'a str,
+        source_type: SourceType,
+    ) -> Self {
+        let unique = UniquePromise::new_for_tests();
+        let mut lexer = Self::new_with_offset(allocator, source_text, source_type, 0, unique);
+        if crate::is_overlong(source_text, 0) {
+            lexer.error(diagnostics::overlong_source());
+        }
+        lexer
+    }
+
+    /// Reset `Lexer` to lex a new source text, reusing its existing allocations.
+    ///
+    /// All state from the previous source is discarded, including errors, trivia and escaped strings,
+    /// but the capacity of internal maps and buffers is retained.
+    ///
+    /// Like `new_for_benchmarks`, this bypasses the `UniquePromise` guarantees, so must NOT be exposed
+    /// in public API. Any `LexerCheckpoint` created before calling this method must not be used after.
+    #[cfg(any(test, feature = "benchmarking"))]
+    pub fn reuse(&mut self, source_text: &'a str, source_type: SourceType) {
+        let unique = UniquePromise::new_for_tests();
+        self.source = Source::new(source_text, 0, unique);
+        self.source_type = source_type;
+        self.restart();
+        if crate::is_overlong(source_text, 0) {
+            self.error(diagnostics::overlong_source());
+        }
+    }
+
+    /// Restart lexing from start of the same source text.
+    ///
+    /// All state from lexing so far is discarded, including errors, trivia and escaped strings,
+    /// and strict mode is reset to the default for the `SourceType`. Comment emission setting is retained.
+    /// Any `LexerCheckpoint` created before calling this method must not be used after.
+    #[allow(dead_code)]
+    pub fn restart(&mut self) {
+        self.source.reset();
+        self.token = Token::new_on_new_line();
+        self.current_token = Token::default();
+        self.errors.clear();
+        self.lookahead.clear();
+        self.context = LexerContext::Regular;
+        self.trivia_builder.clear();
+        self.escaped_strings.clear();
+        self.escaped_templates.clear();
+        self.strict = self.source_type.is_strict();
+    }
+
+    /// Consume the `Lexer`, returning errors, trivia and escaped strings / templates collected
+    /// while lexing.
+    pub fn finish(self) -> LexerReturn<'a> {
+        LexerReturn {
+            errors: self.errors,
+            trivias: self.trivia_builder.build(),
+            escaped_strings: self.escaped_strings,
+            escaped_templates: self.escaped_templates,
+        }
+    }
+
+    /// Set whether comments are returned as tokens.
+    ///
+    /// Formatters want comments in the token stream, parsers don't. Default is `false`.
+    ///
+    /// When `true`, `next_token` returns `Kind::LineComment` and `Kind::BlockComment` tokens,
+    /// spanning the whole comment including its opening and closing. The line break which ends
+    /// a single-line comment is not included. A multi-line comment containing a line break counts
+    /// as a line break itself, so it's the comment token which is marked `is_on_new_line`,
+    /// not the token which follows it.
+    #[allow(dead_code)]
+    pub fn set_emit_comments(&mut self, emit_comments: bool) {
+        self.emit_comments = emit_comments;
+    }
+
+    /// Set whether HTML entities in JSX text are decoded. Default is `false`.
+    ///
+    /// When `true`, character references in `JSXText` tokens (`&amp;`, `&#38;`, `&#x26;`) are decoded,
+    /// and the decoded text is available from `get_string`. Token span still covers the raw text.
+    /// A reference which is unknown or not a valid code point (e.g. `&foo;`) is reported as an error,
+    /// and left undecoded.
+    ///
+    /// Parser does not use this, as the AST stores JSX text as written in source.
+    #[allow(dead_code)]
+    pub fn set_decode_jsx_entities(&mut self, decode_jsx_entities: bool) {
+        self.decode_jsx_entities = decode_jsx_entities;
+    }
+
+    /// Set whether source is lexed as strict mode code.
+    ///
+    /// Defaults to `true` for modules and sources marked `always_strict`, `false` otherwise.
+    /// Can be toggled at any point, to enter or exit a strict context (e.g. a `"use strict"` directive
+    /// or a class body). Only affects tokens lexed afterwards, so not tokens already in lookahead.
+    ///
+    /// In strict mode, the strict mode reserved words `implements`, `interface`, `package`, `private`,
+    /// `protected`, `public` and `static` are lexed as keywords. Outside strict mode, they're lexed
+    /// as `Kind::Ident` in JavaScript sources, but are always keywords in TypeScript.
+    /// `let` and `yield` are lexed as keywords in strict mode even before ES2015
+    /// (see [`Lexer::set_ecma_version`]). Other keywords (e.g. `with`) are lexed to the same `Kind`
+    /// in either mode - it's up to the parser to reject them where they're not allowed in strict
+    /// mode code. Legacy octal literals and escapes are also lexed the same in either mode,
+    /// and are reported by semantic analysis.
+    ///
+    /// The parser calls this on entering and exiting a `"use strict"` function or class body.
+    pub fn set_strict(&mut self, strict: bool) {
+        self.strict = strict;
+    }
+
+    /// Get whether source is currently lexed as strict mode code. See [`Lexer::set_strict`].
+    pub fn is_strict(&self) -> bool {
+        self.strict
+    }
+
+    /// Set ECMAScript version, which determines which words are lexed as keywords.
+    /// Only affects tokens lexed afterwards, so not tokens already in lookahead.
+    pub fn set_ecma_version(&mut self, ecma_version: EcmaVersion) {
+        self.ecma_version = ecma_version;
+    }
+
+    /// Convert `kind` to `Kind::Ident` if it's a keyword which is not reserved in sloppy mode
+    /// in the target ECMAScript version (`let` and `yield` before ES2015).
+    #[inline]
+    fn keyword_for_ecma_version(&self, kind: Kind) -> Kind {
+        if self.ecma_version < EcmaVersion::Es2015
+            && !self.strict
+            && matches!(kind, Kind::Let | Kind::Yield)
+        {
+            Kind::Ident
+        } else {
+            kind
+        }
+    }
+
+    /// Convert `kind` to `Kind::Ident` if it's a word which is only reserved in strict mode code
+    /// (`implements`, `interface`, `package`, `private`, `protected`, `public`, `static`),
+    /// and not currently lexing strict mode code.
+    ///
+    /// TypeScript uses these words as keywords in sloppy mode code too (e.g. `interface` declarations),
+    /// so they are not converted in TypeScript sources.
+    #[inline]
+    fn keyword_for_strict_mode(&self, kind: Kind) -> Kind {
+        if !self.strict && !self.source_type.is_typescript() && kind.is_future_reserved_keyword() {
+            Kind::Ident
+        } else {
+            kind
+        }
+    }
+
+    /// Get an iterator over remaining tokens, up to and including `Eof`.
+    #[allow(dead_code)]
+    pub fn tokens(&mut self) -> TokenStream<'_, 'a> {
+        TokenStream { lexer: self, done: false }
+    }
+
+    /// Lex remaining tokens, up to and including `Eof`, appending kind and span of each to `spans`.
+    ///
+    /// `spans` can be reused across sources, to avoid allocating a new `Vec` for each.
+    #[allow(dead_code)]
+    pub fn lex_all_into(&mut self, spans: &mut Vec<(Kind, Span)>) {
+        spans.extend(self.tokens().map(|token| (token.kind, token.span())));
     }
 
     /// Remaining string from `Source`
@@ -147,24 +421,108 @@ impl<'a> Lexer<'a> {
         self.source.remaining()
     }
 
+    /// Get offset of start of source text. Usually 0.
+    pub fn base_offset(&self) -> u32 {
+        self.source.base_offset()
+    }
+
+    /// Get source text between `start` and `end` offsets.
+    ///
+    /// Offsets include base offset, same as offsets in `Token`s and `Span`s.
+    ///
+    /// # Panics
+    /// Panics if `start` or `end` are out of bounds, or not on UTF-8 character boundaries.
+    pub fn source_text_between(&self, start: u32, end: u32) -> &'a str {
+        self.source.str_between_offsets(start, end)
+    }
+
+    /// Get offsets of start of each line in source text, for converting offsets in diagnostics
+    /// to line numbers. Scans the whole source, so result should be cached by the caller.
+    #[allow(dead_code)]
+    pub fn compute_line_starts(&self) -> Box<[u32]> {
+        self.source.compute_line_starts()
+    }
+
+    /// Comments and irregular whitespace found so far.
+    ///
+    /// Trivia is recorded only once, even if the lexer is rewound and lexes the same source again.
+    #[allow(dead_code)]
+    pub fn trivia(&self) -> &TriviaBuilder {
+        &self.trivia_builder
+    }
+
     /// Creates a checkpoint storing the current lexer state.
     /// Use `rewind` to restore the lexer to the state stored in the checkpoint.
     pub fn checkpoint(&self) -> LexerCheckpoint<'a> {
         LexerCheckpoint {
             position: self.source.position(),
             token: self.token,
+            current_token: self.current_token,
             errors_pos: self.errors.len(),
+            strict: self.strict,
         }
     }
 
     /// Rewinds the lexer to the same state as when the passed in `checkpoint` was created.
+    ///
+    /// # Panics
+    /// Panics if `checkpoint` is not a valid position in this `Lexer`'s source,
+    /// which can happen if it was created by another `Lexer`.
     pub fn rewind(&mut self, checkpoint: LexerCheckpoint<'a>) {
         self.errors.truncate(checkpoint.errors_pos);
-        self.source.set_position(checkpoint.position);
+        self.source.set_position_checked(checkpoint.position);
         self.token = checkpoint.token;
+        self.current_token = checkpoint.current_token;
+        self.strict = checkpoint.strict;
         self.lookahead.clear();
     }
 
+    /// Get the token whose span contains `offset`.
+    ///
+    /// Returns `None` if `offset` is in whitespace or a comment, is at end of source,
+    /// or is not a valid offset in source (out of bounds, or not on a UTF-8 character boundary).
+    ///
+    /// Lexes forward from the current position if `offset` is not before it,
+    /// otherwise from start of source. Lexer state is restored afterwards, including errors,
+    /// trivia, and escaped strings and templates.
+    /// Tokens are lexed without parser context, same as [`Lexer::tokens`].
+    #[allow(dead_code)]
+    pub fn token_at(&mut self, offset: u32) -> Option<Token> {
+        self.source.position_at_offset(offset)?;
+
+        let checkpoint = self.checkpoint();
+        let trivia_checkpoint = self.trivia_builder.checkpoint();
+        let escaped_strings_len = self.escaped_strings.len();
+        let escaped_templates_len = self.escaped_templates.len();
+        if offset < self.offset() {
+            self.source.reset();
+            self.token = Token::new_on_new_line();
+        }
+        self.lookahead.clear();
+
+        let found = loop {
+            let token = self.next_token();
+            // Remove escaped values of tokens which had not been lexed before.
+            // Tokens which had been lexed before overwrite their entry with the same value.
+            if self.escaped_strings.len() > escaped_strings_len {
+                self.escaped_strings.remove(&token.start);
+            }
+            if self.escaped_templates.len() > escaped_templates_len {
+                self.escaped_templates.remove(&token.start);
+            }
+            if token.kind == Kind::Eof || token.start > offset {
+                break None;
+            }
+            if offset < token.end {
+                break Some(token);
+            }
+        };
+
+        self.rewind(checkpoint);
+        self.trivia_builder.rewind(trivia_checkpoint);
+        found
+    }
+
     /// Find the nth lookahead token lazily
     pub fn lookahead(&mut self, n: u8) -> Token {
         let n = n as usize;
@@ -175,6 +533,7 @@ impl<'a> Lexer<'a> {
         }
 
         let position = self.source.position();
+        let current_token = self.current_token;
 
         if let Some(lookahead) = self.lookahead.back() {
             self.source.set_position(lookahead.position);
@@ -194,10 +553,20 @@ impl<'a> Lexer<'a> {
         // It's already in same state as it was at start of this function.
 
         self.source.set_position(position);
+        self.current_token = current_token;
 
         self.lookahead[n - 1].token
     }
 
+    /// Get the token most recently returned by the lexer.
+    ///
+    /// Tokens produced by `lookahead` are not included until they're returned by `next_token`.
+    /// Before any token is read, returns `Token::default()`.
+    #[allow(dead_code)]
+    pub fn current_token(&self) -> Token {
+        self.current_token
+    }
+
     /// Set context
     pub fn set_context(&mut self, context: LexerContext) {
         self.context = context;
@@ -207,6 +576,7 @@ impl<'a> Lexer<'a> {
     pub fn next_token(&mut self) -> Token {
         if let Some(lookahead) = self.lookahead.pop_front() {
             self.source.set_position(lookahead.position);
+            self.current_token = lookahead.token;
             return lookahead.token;
         }
         let kind = self.read_next_token();
@@ -219,6 +589,7 @@ impl<'a> Lexer<'a> {
         debug_assert!(self.token.start <= self.token.end);
         let token = self.token;
         self.token = Token::default();
+        self.current_token = token;
         token
     }
 
@@ -278,13 +649,16 @@ impl<'a> Lexer<'a> {
         Span::new(offset, offset)
     }
 
-    /// Return `IllegalCharacter` Error or `UnexpectedEnd` if EOF
-    fn unexpected_err(&mut self) {
-        let offset = self.current_offset();
-        match self.peek() {
-            Some(c) => self.error(diagnostics::invalid_character(c, offset)),
-            None => self.error(diagnostics::unexpected_end(offset)),
+    /// Consume a run of consecutive invalid characters, and report a single error spanning all of them.
+    ///
+    /// First invalid char `c` must have been consumed already.
+    /// Diagnostic underlines the whole bad region, rather than reporting 1 error per char.
+    fn invalid_characters(&mut self, c: char) -> Kind {
+        while self.peek().is_some_and(is_invalid_character) {
+            self.consume_char();
         }
+        self.error(diagnostics::invalid_character(c, self.unterminated_range()));
+        Kind::Undetermined
     }
 
     /// Read each char and set the current token
@@ -307,8 +681,713 @@ impl<'a> Lexer<'a> {
     }
 }
 
+/// Return `true` if `c` cannot start any token, and is not whitespace or a line terminator.
+fn is_invalid_character(c: char) -> bool {
+    if c.is_ascii() {
+        // ASCII control characters, except whitespace and line breaks
+        matches!(c, '\0'..='\u{8}' | '\u{e}'..='\u{1f}' | '\u{7f}')
+    } else {
+        !is_identifier_start_unicode(c)
+            && !is_irregular_whitespace(c)
+            && !is_irregular_line_terminator(c)
+    }
+}
+
 /// Call a closure while hinting to compiler that this branch is rarely taken.
 #[cold]
-pub fn cold_branch<F: FnOnce() -> T, T>(f: F) -> T {
+pub(crate) fn cold_branch<F: FnOnce() -> T, T>(f: F) -> T {
     f()
 }
+
+#[cfg(test)]
+#[allow(clippy::cast_possible_truncation)]
+mod test {
+    use oxc_allocator::Allocator;
+    use oxc_ast::CommentKind;
+    use oxc_span::{SourceType, Span};
+
+    use super::{
+        parse_int,
+        tester::{error_spans, kinds, lex, lex_as, lex_to_end},
+        EcmaVersion, Kind, Lexer, Token, UniquePromise,
+    };
+
+    #[test]
+    fn byte_order_mark() {
+        let allocator = Allocator::default();
+        let (tokens, lexer) = lex(&allocator, "\u{FEFF}var x");
+        assert_eq!(kinds(&tokens), [Kind::Var, Kind::Ident]);
+        assert_eq!(tokens[0].span(), Span::new(3, 6));
+        assert!(lexer.errors.is_empty());
+        assert!(lexer.trivia_builder.build().irregular_whitespaces().is_empty());
+
+        // BOM anywhere else is irregular whitespace
+        let (tokens, lexer) = lex(&allocator, "var\u{FEFF}x");
+        assert_eq!(kinds(&tokens), [Kind::Var, Kind::Ident]);
+        assert_eq!(tokens[1].span(), Span::new(6, 7));
+        assert_eq!(lexer.trivia_builder.build().irregular_whitespaces(), &[Span::new(3, 6)]);
+    }
+
+    #[test]
+    fn new_recovering() {
+        let allocator = Allocator::default();
+        let nested_templates = format!("{}`", "`${".repeat(10_000));
+        for source in [
+            "'abc",
+            "\"abc\ndef\"",
+            "`abc",
+            "/* abc",
+            "0x",
+            "1e",
+            "\\u{",
+            "a\\u",
+            "\u{0}\u{1}",
+            "'\\x' '\\u{110000}' `\\u{` ",
+            &nested_templates,
+        ] {
+            let mut lexer = Lexer::new_recovering(&allocator, source, SourceType::default());
+            let mut count = 0;
+            while lexer.next_token().kind != Kind::Eof {
+                count += 1;
+                assert!(count <= source.len(), "{source:?}");
+            }
+            assert!(!lexer.errors.is_empty(), "{source:?}");
+        }
+    }
+
+    #[test]
+    #[should_panic(expected = "`SourcePosition` is not valid for `Source`")]
+    fn rewind_to_checkpoint_from_different_lexer() {
+        let allocator = Allocator::default();
+        let (source1, source2) = (String::from("foo bar"), String::from("x"));
+        let mut lexer1 = Lexer::new_recovering(&allocator, &source1, SourceType::default());
+        let mut lexer2 = Lexer::new_recovering(&allocator, &source2, SourceType::default());
+        lexer1.next_token();
+        lexer2.rewind(lexer1.checkpoint());
+    }
+
+    #[test]
+    fn lex_all_into() {
+        let allocator = Allocator::default();
+        let mut spans = Vec::with_capacity(16);
+        let capacity = spans.capacity();
+
+        let mut lexer = Lexer::new_for_tests(&allocator, "let x = 1;", SourceType::default());
+        lexer.lex_all_into(&mut spans);
+        assert_eq!(
+            spans,
+            [
+                (Kind::Let, Span::new(0, 3)),
+                (Kind::Ident, Span::new(4, 5)),
+                (Kind::Eq, Span::new(6, 7)),
+                (Kind::Decimal, Span::new(8, 9)),
+                (Kind::Semicolon, Span::new(9, 10)),
+                (Kind::Eof, Span::new(10, 10)),
+            ]
+        );
+
+        // Reuse buffer for another source
+        spans.clear();
+        let mut lexer = Lexer::new_for_tests(&allocator, "a=>b", SourceType::default());
+        lexer.lex_all_into(&mut spans);
+        assert_eq!(
+            spans,
+            [
+                (Kind::Ident, Span::new(0, 1)),
+                (Kind::Arrow, Span::new(1, 3)),
+                (Kind::Ident, Span::new(3, 4)),
+                (Kind::Eof, Span::new(4, 4)),
+            ]
+        );
+        assert_eq!(spans.capacity(), capacity);
+
+        // Appends to existing contents, and only lexes remaining tokens
+        let mut lexer = Lexer::new_for_tests(&allocator, "x y", SourceType::default());
+        lexer.next_token();
+        lexer.lex_all_into(&mut spans);
+        assert_eq!(spans.len(), 6);
+        assert_eq!(spans[4..], [(Kind::Ident, Span::new(2, 3)), (Kind::Eof, Span::new(3, 3))]);
+    }
+
+    #[test]
+    fn reuse() {
+        let allocator = Allocator::default();
+        let summarize = |lexer: &mut Lexer<'_>| {
+            lex_to_end(lexer)
+                .into_iter()
+                .map(|token| (token.kind, token.span(), token.is_escaped()))
+                .collect::<Vec<_>>()
+        };
+
+        let sources = [
+            ("'\\x41' + `\\u{}${x}` /* a */ + 1_", SourceType::default()),
+            ("type T = \"\\t\"; // b\n`\\n`", SourceType::default().with_typescript(true)),
+        ];
+
+        let (first_source, first_source_type) = sources[0];
+        let mut lexer = Lexer::new_recovering(&allocator, first_source, first_source_type);
+        let mut capacities = None;
+        for (source, source_type) in sources {
+            if let Some((strings_capacity, templates_capacity)) = capacities {
+                lexer.reuse(source, source_type);
+                assert!(lexer.errors.is_empty());
+                assert!(lexer.trivia().comments().next().is_none());
+                assert!(lexer.escaped_strings.is_empty());
+                assert!(lexer.escaped_templates.is_empty());
+                assert_eq!(lexer.escaped_strings.capacity(), strings_capacity);
+                assert_eq!(lexer.escaped_templates.capacity(), templates_capacity);
+            }
+
+            let mut fresh = Lexer::new_for_tests(&allocator, source, source_type);
+            assert_eq!(summarize(&mut lexer), summarize(&mut fresh), "{source}");
+            assert_eq!(error_spans(&lexer), error_spans(&fresh), "{source}");
+            assert_eq!(
+                lexer.trivia().comments().collect::<Vec<_>>(),
+                fresh.trivia().comments().collect::<Vec<_>>()
+            );
+            assert_eq!(lexer.escaped_strings, fresh.escaped_strings);
+            assert_eq!(lexer.escaped_templates, fresh.escaped_templates);
+
+            capacities =
+                Some((lexer.escaped_strings.capacity(), lexer.escaped_templates.capacity()));
+        }
+    }
+
+    #[test]
+    fn restart() {
+        let allocator = Allocator::default();
+        let summarize = |lexer: &mut Lexer<'_>| {
+            lex_to_end(lexer)
+                .into_iter()
+                .map(|token| (token.kind, token.span(), token.is_escaped()))
+                .collect::<Vec<_>>()
+        };
+
+        let source = "\u{FEFF}'\\x41' + 0777 /* a */ + `\\n` // b\n1_";
+        for base_offset in [0, 100] {
+            let mut lexer = Lexer::new_with_offset(
+                &allocator,
+                source,
+                SourceType::default(),
+                base_offset,
+                UniquePromise::new_for_tests(),
+            );
+            let tokens = summarize(&mut lexer);
+            let errors = error_spans(&lexer);
+            let comments = lexer.trivia().comments().collect::<Vec<_>>();
+            let escaped_strings = lexer.escaped_strings.clone();
+            assert_eq!(tokens[0].1.start, base_offset + 3);
+
+            lexer.restart();
+            assert!(lexer.errors.is_empty());
+            assert!(lexer.trivia().comments().next().is_none());
+            assert!(lexer.escaped_strings.is_empty());
+            assert!(lexer.escaped_templates.is_empty());
+
+            // Strict mode set mid-stream is reset
+            lexer.set_strict(true);
+            lexer.restart();
+
+            assert_eq!(summarize(&mut lexer), tokens);
+            assert_eq!(error_spans(&lexer), errors);
+            assert_eq!(lexer.trivia().comments().collect::<Vec<_>>(), comments);
+            assert_eq!(lexer.escaped_strings, escaped_strings);
+        }
+    }
+
+    #[test]
+    fn legacy_octal() {
+        let allocator = Allocator::default();
+        let script = SourceType::default().with_module(false);
+        let module = SourceType::default().with_module(true);
+        let strict_script = script.with_always_strict(true);
+
+        // Legacy octals are lexed the same in all source types.
+        // They're syntax errors in strict mode code, but that's reported by semantic analysis.
+        for source_type in [script, module, strict_script] {
+            // Legacy octal literal
+            let (tokens, lexer) = lex_as(&allocator, "x = 0777;", source_type);
+            assert_eq!(kinds(&tokens), [Kind::Ident, Kind::Eq, Kind::Octal, Kind::Semicolon]);
+            assert_eq!(parse_int("0777", Kind::Octal), Ok(511.0));
+            assert!(lexer.errors.is_empty());
+
+            // Decimal with leading zero is not an octal literal
+            let mut lexer = Lexer::new_for_tests(&allocator, "089", source_type);
+            assert_eq!(lexer.next_token().kind, Kind::Decimal);
+            assert!(lexer.errors.is_empty());
+
+            // Legacy octal escapes
+            for (source, value) in
+                [("'\\07'", "\u{7}"), ("'a\\08'", "a\08"), ("'\\377\\1'", "\u{ff}\u{1}")]
+            {
+                let mut lexer = Lexer::new_for_tests(&allocator, source, source_type);
+                let token = lexer.next_token();
+                assert_eq!(token.kind, Kind::Str, "{source}");
+                assert_eq!(lexer.get_string(token), value, "{source}");
+                assert!(lexer.errors.is_empty(), "{source}");
+            }
+
+            // `\0` not followed by a digit is not an octal escape
+            let mut lexer = Lexer::new_for_tests(&allocator, "'\\0'", source_type);
+            assert_eq!(lexer.next_token().kind, Kind::Str);
+            assert!(lexer.errors.is_empty());
+        }
+    }
+
+    #[test]
+    fn set_strict() {
+        let allocator = Allocator::default();
+        let script = SourceType::default().with_module(false);
+        let module = SourceType::default().with_module(true);
+
+        // Before ES2015, `let` and `yield` are only reserved in strict mode
+        for source_type in [script, module] {
+            for is_strict in [false, true] {
+                let mut lexer = Lexer::new_for_tests(&allocator, "let yield", source_type);
+                lexer.set_ecma_version(EcmaVersion::Es5);
+                lexer.set_strict(is_strict);
+                assert_eq!(lexer.is_strict(), is_strict);
+                let tokens = lex_to_end(&mut lexer);
+                let expected = if is_strict { [Kind::Let, Kind::Yield] } else { [Kind::Ident; 2] };
+                assert_eq!(kinds(&tokens), expected, "{source_type:?} {is_strict}");
+            }
+        }
+
+        // Toggle mid-stream
+        let mut lexer = Lexer::new_for_tests(&allocator, "let let let", script);
+        lexer.set_ecma_version(EcmaVersion::Es5);
+        let first = lexer.next_token().kind;
+        lexer.set_strict(true);
+        let second = lexer.next_token().kind;
+        lexer.set_strict(false);
+        let third = lexer.next_token().kind;
+        assert_eq!([first, second, third], [Kind::Ident, Kind::Let, Kind::Ident]);
+    }
+
+    #[test]
+    fn set_strict_reserved_words() {
+        let allocator = Allocator::default();
+        let script = SourceType::default().with_module(false);
+        let module = SourceType::default().with_module(true);
+        let source = "implements interface package private protected public static let yield with \
+            p\\u0075blic";
+        let reserved = [
+            Kind::Implements,
+            Kind::Interface,
+            Kind::Package,
+            Kind::Private,
+            Kind::Protected,
+            Kind::Public,
+            Kind::Static,
+            Kind::Let,
+            Kind::Yield,
+            Kind::With,
+            Kind::Public,
+        ];
+        // In sloppy mode, only `let` and `yield` (contextual keywords) and `with` (always reserved)
+        // are keywords
+        let mut sloppy = [Kind::Ident; 11];
+        sloppy[7..10].copy_from_slice(&[Kind::Let, Kind::Yield, Kind::With]);
+
+        for (source_type, is_strict, expected) in [
+            (script, false, sloppy),
+            (script, true, reserved),
+            (module, false, sloppy),
+            (module, true, reserved),
+            // Always keywords in TypeScript
+            (script.with_typescript(true), false, reserved),
+        ] {
+            let mut lexer = Lexer::new_for_tests(&allocator, source, source_type);
+            lexer.set_strict(is_strict);
+            let tokens = lex_to_end(&mut lexer);
+            assert_eq!(kinds(&tokens), expected, "{source_type:?} {is_strict}");
+            assert!(lexer.errors.is_empty());
+        }
+
+        // Toggle mid-stream
+        let mut lexer = Lexer::new_for_tests(&allocator, "static static static", script);
+        let first = lexer.next_token().kind;
+        lexer.set_strict(true);
+        let second = lexer.next_token().kind;
+        lexer.set_strict(false);
+        let third = lexer.next_token().kind;
+        assert_eq!([first, second, third], [Kind::Ident, Kind::Static, Kind::Ident]);
+    }
+
+    #[test]
+    fn ecma_version() {
+        let allocator = Allocator::default();
+        let script = SourceType::default().with_module(false);
+        let module = SourceType::default().with_module(true);
+        let source = "let yield l\\u0065t y\\u0069eld";
+
+        for (ecma_version, source_type, is_keyword) in [
+            (EcmaVersion::Latest, script, true),
+            (EcmaVersion::Es2015, script, true),
+            (EcmaVersion::Es5, script, false),
+            // Reserved in strict mode in ES5 too
+            (EcmaVersion::Es5, module, true),
+        ] {
+            let mut lexer = Lexer::new_for_tests(&allocator, source, source_type);
+            lexer.set_ecma_version(ecma_version);
+            let tokens = lex_to_end(&mut lexer);
+            let expected = if is_keyword {
+                [Kind::Let, Kind::Yield, Kind::Let, Kind::Yield]
+            } else {
+                [Kind::Ident; 4]
+            };
+            assert_eq!(kinds(&tokens), expected, "{ecma_version:?} {source_type:?}");
+            assert!(lexer.errors.is_empty());
+        }
+
+        // Other keywords are unaffected
+        let mut lexer = Lexer::new_for_tests(&allocator, "class const lets", script);
+        lexer.set_ecma_version(EcmaVersion::Es5);
+        let tokens = lex_to_end(&mut lexer);
+        assert_eq!(kinds(&tokens), [Kind::Class, Kind::Const, Kind::Ident]);
+    }
+
+    #[test]
+    fn current_token() {
+        let allocator = Allocator::default();
+        let mut lexer = Lexer::new_for_tests(&allocator, "a + b; c", SourceType::default());
+        assert_eq!(lexer.current_token().kind, Kind::Eof);
+        assert_eq!(lexer.current_token().span(), Span::new(0, 0));
+
+        let token = lexer.next_token();
+        assert_eq!(lexer.current_token().span(), token.span());
+        assert_eq!(lexer.current_token().kind, Kind::Ident);
+
+        // Lookahead does not change current token
+        assert_eq!(lexer.lookahead(2).kind, Kind::Ident);
+        assert_eq!(lexer.current_token().span(), token.span());
+
+        // Token returned from lookahead queue is current
+        let checkpoint = lexer.checkpoint();
+        let token = lexer.next_token();
+        assert_eq!(token.kind, Kind::Plus);
+        assert_eq!(lexer.current_token().span(), token.span());
+        assert_eq!(lexer.current_token().kind, Kind::Plus);
+        let token = lexer.next_token();
+        assert_eq!(
+            (lexer.current_token().kind, lexer.current_token().span()),
+            (Kind::Ident, token.span())
+        );
+        let token = lexer.next_token();
+        assert_eq!(
+            (lexer.current_token().kind, lexer.current_token().span()),
+            (Kind::Semicolon, token.span())
+        );
+
+        // Rewind restores current token
+        lexer.rewind(checkpoint);
+        assert_eq!(lexer.current_token().span(), Span::new(0, 1));
+
+        while lexer.next_token().kind != Kind::Eof {}
+        assert_eq!(lexer.current_token().kind, Kind::Eof);
+        assert_eq!(lexer.current_token().span(), Span::new(8, 8));
+
+        lexer.restart();
+        assert_eq!(lexer.current_token().span(), Span::new(0, 0));
+    }
+
+    #[test]
+    fn token_at() {
+        let allocator = Allocator::default();
+        let source = "foo = 'bar é' + /* c */ qux;";
+        let (tokens, _) = lex(&allocator, source);
+        let mut lexer = Lexer::new_for_tests(&allocator, source, SourceType::default());
+        let kind_at = |lexer: &mut Lexer, offset| lexer.token_at(offset).map(|token| token.kind);
+
+        // Inside identifier
+        let token = lexer.token_at(1).unwrap();
+        assert_eq!((token.kind, token.span()), (Kind::Ident, Span::new(0, 3)));
+        // Inside string literal
+        let token = lexer.token_at(9).unwrap();
+        assert_eq!((token.kind, token.span()), (Kind::Str, Span::new(6, 14)));
+        assert_eq!(lexer.get_string(token), "bar é");
+        // At token boundaries
+        assert_eq!(kind_at(&mut lexer, 0), Some(Kind::Ident));
+        assert_eq!(kind_at(&mut lexer, 4), Some(Kind::Eq));
+        assert_eq!(kind_at(&mut lexer, 28), Some(Kind::Semicolon));
+        // Whitespace, comment, end of source, mid-char, out of bounds
+        for offset in [3, 19, 29, 12, 100] {
+            assert_eq!(kind_at(&mut lexer, offset), None, "{offset}");
+        }
+
+        // Lexer state is restored, whether `offset` is before or after current position
+        for (i, expected) in tokens.iter().enumerate() {
+            assert_eq!(kind_at(&mut lexer, expected.start), Some(expected.kind));
+            let token = lexer.next_token();
+            assert_eq!((token.kind, token.span()), (expected.kind, expected.span()), "{i}");
+            assert_eq!(kind_at(&mut lexer, 0), Some(Kind::Ident));
+        }
+        assert_eq!(lexer.next_token().kind, Kind::Eof);
+        assert!(lexer.errors.is_empty());
+        assert_eq!(lexer.trivia_builder.comments().count(), 1);
+    }
+
+    #[test]
+    fn token_at_restores_trivia_and_escapes() {
+        let allocator = Allocator::default();
+        let source = "a /* 1 */ '\\x41' \u{a0} // 2\n`\\x42` b";
+        let mut lexer = Lexer::new_for_tests(&allocator, source, SourceType::default());
+        let state = |lexer: &Lexer| {
+            let mut strings = lexer.escaped_strings.keys().copied().collect::<Vec<_>>();
+            let mut templates = lexer.escaped_templates.keys().copied().collect::<Vec<_>>();
+            strings.sort_unstable();
+            templates.sort_unstable();
+            (
+                lexer.trivia().comments().count(),
+                lexer.trivia().irregular_whitespaces().len(),
+                strings,
+                templates,
+            )
+        };
+
+        let b_start = source.find('b').unwrap() as u32;
+        loop {
+            let before = state(&lexer);
+            // Lex forward past trivia and escapes, then back from start of source
+            for offset in [b_start, 0, b_start] {
+                assert!(lexer.token_at(offset).is_some());
+                assert_eq!(state(&lexer), before);
+            }
+            if lexer.next_token().kind == Kind::Eof {
+                break;
+            }
+        }
+        let (comments, irregular_whitespaces, strings, templates) = state(&lexer);
+        assert_eq!((comments, irregular_whitespaces, strings.len(), templates.len()), (2, 1, 1, 1));
+    }
+
+    #[test]
+    fn lookahead_and_rewind() {
+        let allocator = Allocator::default();
+        let source = "a\n'\\x41' /* c */ + foo(1)\n  <<= `t`";
+        let summary =
+            |token: Token| (token.kind, token.span(), token.is_on_new_line, token.escaped);
+
+        let (tokens, _) = lex(&allocator, source);
+        let expected = tokens.into_iter().map(summary).collect::<Vec<_>>();
+        assert!(expected[1].2 && expected[1].3);
+
+        // Interleave lookahead, checkpoints and rewinds. Tokens returned are unchanged.
+        let mut lexer = Lexer::new_for_tests(&allocator, source, SourceType::default());
+        let mut actual = vec![];
+        loop {
+            let checkpoint = lexer.checkpoint();
+            let peeked = (1..=3).map(|n| summary(lexer.lookahead(n))).collect::<Vec<_>>();
+            let token = lexer.next_token();
+            assert_eq!(summary(token), peeked[0]);
+            if token.kind != Kind::Eof {
+                assert_eq!(summary(lexer.next_token()), peeked[1]);
+            }
+            lexer.rewind(checkpoint);
+
+            let token = lexer.next_token();
+            if token.kind == Kind::Eof {
+                break;
+            }
+            actual.push(summary(token));
+        }
+        assert_eq!(actual, expected);
+        assert!(lexer.errors.is_empty());
+
+        // Re-lexing `<<=` as `<` for TypeScript
+        let index = expected.iter().position(|token| token.0 == Kind::ShiftLeftEq).unwrap();
+        let mut lexer = Lexer::new_for_tests(&allocator, source, SourceType::default());
+        for _ in 0..index {
+            lexer.next_token();
+        }
+        let checkpoint = lexer.checkpoint();
+        let token = lexer.next_token();
+        assert_eq!(lexer.lookahead(1).kind, Kind::NoSubstitutionTemplate);
+        let token = lexer.re_lex_as_typescript_l_angle(token.kind);
+        let start = expected[index].1.start;
+        assert_eq!((token.kind, token.span()), (Kind::LAngle, Span::new(start, start + 1)));
+        assert_eq!(lexer.next_token().kind, Kind::LtEq);
+        lexer.rewind(checkpoint);
+        assert_eq!(summary(lexer.next_token()), expected[index]);
+    }
+
+    #[test]
+    fn is_on_new_line() {
+        let allocator = Allocator::default();
+        for (source, expected) in [
+            ("a b", false),
+            ("a /* */ b", false),
+            ("a /* x */ /* y */ b", false),
+            ("a\nb", true),
+            ("a\r\nb", true),
+            ("a\u{2029}b", true),
+            ("a // x\nb", true),
+            ("a /* \n */ b", true),
+            ("a /* \r */ b", true),
+            ("a /* \u{2028} */ b", true),
+            ("a /* x */ /*\n*/ /* y */ b", true),
+            ("a /** very long comment which takes slower path \n */ b", true),
+        ] {
+            let (tokens, lexer) = lex(&allocator, source);
+            assert_eq!(kinds(&tokens), [Kind::Ident, Kind::Ident], "{source:?}");
+            assert_eq!(tokens[1].is_on_new_line, expected, "{source:?}");
+            assert!(lexer.errors.is_empty());
+        }
+
+        // When emitting comments, a multi-line comment containing a line break is itself
+        // on a new line, and the token after it is not. First token is always on a new line.
+        let source = "a /* \n */ b /* */ c";
+        let mut lexer = Lexer::new_for_tests(&allocator, source, SourceType::default());
+        lexer.set_emit_comments(true);
+        let tokens =
+            lexer.tokens().map(|token| (token.kind, token.is_on_new_line)).collect::<Vec<_>>();
+        assert_eq!(
+            tokens,
+            [
+                (Kind::Ident, true),
+                (Kind::BlockComment, true),
+                (Kind::Ident, false),
+                (Kind::BlockComment, false),
+                (Kind::Ident, false),
+                (Kind::Eof, false),
+            ]
+        );
+    }
+
+    #[test]
+    fn tokens() {
+        let allocator = Allocator::default();
+        let mut lexer = Lexer::new_for_tests(&allocator, "let x = 1;", SourceType::default());
+        let tokens = lexer.tokens().collect::<Vec<_>>();
+        assert_eq!(
+            kinds(&tokens),
+            [Kind::Let, Kind::Ident, Kind::Eq, Kind::Decimal, Kind::Semicolon, Kind::Eof]
+        );
+
+        // Iterator ends after `Eof`
+        let mut lexer = Lexer::new_for_tests(&allocator, "x", SourceType::default());
+        let mut tokens = lexer.tokens();
+        assert_eq!(tokens.next().map(|token| token.kind), Some(Kind::Ident));
+        assert_eq!(tokens.next().map(|token| token.kind), Some(Kind::Eof));
+        assert!(tokens.next().is_none());
+        assert!(tokens.next().is_none());
+
+        // Empty source
+        let mut lexer = Lexer::new_for_tests(&allocator, "", SourceType::default());
+        assert_eq!(kinds(&lexer.tokens().collect::<Vec<_>>()), [Kind::Eof]);
+    }
+
+    #[test]
+    fn base_offset() {
+        let allocator = Allocator::default();
+        let source = "var x";
+        let mut lexer = Lexer::new_with_offset(
+            &allocator,
+            source,
+            SourceType::default(),
+            100,
+            UniquePromise::new_for_tests(),
+        );
+        let tokens = lexer.tokens().collect::<Vec<_>>();
+        assert_eq!(kinds(&tokens), [Kind::Var, Kind::Ident, Kind::Eof]);
+        let spans = tokens.iter().map(Token::span).collect::<Vec<_>>();
+        assert_eq!(spans, [Span::new(100, 103), Span::new(104, 105), Span::new(105, 105)]);
+
+        // Strings, templates, trivia and errors
+        let source = "'\\x61' + 'b' // x\n`c` + `\\u0064` + \"";
+        let mut lexer = Lexer::new_with_offset(
+            &allocator,
+            source,
+            SourceType::default(),
+            1000,
+            UniquePromise::new_for_tests(),
+        );
+        let tokens = lexer.tokens().collect::<Vec<_>>();
+        assert_eq!(tokens[0].start, 1000);
+        assert_eq!(lexer.get_string(tokens[0]), "a");
+        assert_eq!(lexer.get_string(tokens[2]), "b");
+        assert_eq!(lexer.template_cooked(tokens[3]), Some("c"));
+        assert_eq!(lexer.template_cooked(tokens[5]), Some("d"));
+        assert_eq!(lexer.template_raw(tokens[5]), "\\u0064");
+        let comment_start = source.find("//").unwrap() as u32 + 1002;
+        assert_eq!(
+            lexer.trivia().comments().collect::<Vec<_>>(),
+            [(Span::new(comment_start, comment_start + 2), CommentKind::SingleLine)]
+        );
+        let end = source.len() as u32 + 1000;
+        assert_eq!(error_spans(&lexer), [Span::new(end - 1, end)]);
+
+        // Offsets map back to positions in source
+        let mut lexer = Lexer::new_with_offset(
+            &allocator,
+            "#!x\nab",
+            SourceType::default(),
+            10,
+            UniquePromise::new_for_tests(),
+        );
+        assert_eq!(lexer.next_token().kind, Kind::HashbangComment);
+        assert!(lexer.source.position_at_offset(9).is_none());
+        assert!(lexer.source.position_at_offset(17).is_none());
+        lexer.source.set_offset(15);
+        assert_eq!(lexer.remaining(), "b");
+        assert_eq!(lexer.next_token().span(), Span::new(15, 16));
+    }
+
+    #[test]
+    fn compute_line_starts() {
+        let allocator = Allocator::default();
+        let source = "a\nb\r\nc\rd\u{2028}e\u{2029}f\r\n\n";
+        let lexer = Lexer::new_for_tests(&allocator, source, SourceType::default());
+        let line_starts = lexer.compute_line_starts();
+        assert_eq!(&*line_starts, &[0, 2, 5, 7, 11, 15, 18, 19]);
+        let line_breaks = source.matches(['\n', '\r', '\u{2028}', '\u{2029}']).count()
+            - source.matches("\r\n").count();
+        assert_eq!(line_starts.len(), line_breaks + 1);
+
+        // Offsets include base offset
+        let lexer = Lexer::new_with_offset(
+            &allocator,
+            "a\nb",
+            SourceType::default(),
+            10,
+            UniquePromise::new_for_tests(),
+        );
+        assert_eq!(&*lexer.compute_line_starts(), &[10, 12]);
+
+        let lexer = Lexer::new_for_tests(&allocator, "", SourceType::default());
+        assert_eq!(&*lexer.compute_line_starts(), &[0]);
+    }
+
+    #[test]
+    fn base_offset_overflow() {
+        // Source text is substituted, same as source exceeding `MAX_LEN`
+        let allocator = Allocator::default();
+        let unique = UniquePromise::new_for_tests();
+        let mut lexer =
+            Lexer::new_with_offset(&allocator, "xx", SourceType::default(), u32::MAX - 1, unique);
+        assert_eq!(lexer.base_offset(), 0);
+        assert_eq!(lexer.next_token().span(), Span::new(0, 1));
+
+        let unique = UniquePromise::new_for_tests();
+        let lexer =
+            Lexer::new_with_offset(&allocator, "xx", SourceType::default(), u32::MAX - 2, unique);
+        assert_eq!(lexer.base_offset(), u32::MAX - 2);
+    }
+
+    #[test]
+    fn finish() {
+        let allocator = Allocator::default();
+        let source = "'\\x61b' + `c\\u0064` + `\\u{` // comment\n'plain'";
+        let (tokens, lexer) = lex(&allocator, source);
+        let ret = lexer.finish();
+
+        assert_eq!(ret.escaped_strings.len(), 1);
+        assert_eq!(ret.escaped_strings[&tokens[0].start], "ab");
+        assert_eq!(ret.escaped_templates.len(), 2);
+        assert_eq!(ret.escaped_templates[&tokens[2].start], Some("cd"));
+        // Invalid escape
+        assert_eq!(ret.escaped_templates[&tokens[4].start], None);
+        assert!(ret.errors.is_empty());
+        assert_eq!(ret.trivias.comments().count(), 1);
+    }
+}
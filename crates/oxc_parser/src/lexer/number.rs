@@ -7,6 +7,10 @@ use std::borrow::Cow;
 use super::kind::Kind;
 
 // the string passed in has `_` removed from the lexer
+/// Parse source text of a numeric literal token of `kind` to a number.
+///
+/// # Errors
+/// Returns an error if `s` is not a valid decimal literal.
 pub fn parse_int(s: &str, kind: Kind) -> Result<f64, &'static str> {
     if kind == Kind::Decimal {
         return parse_float(s);
@@ -28,6 +32,10 @@ pub fn parse_int(s: &str, kind: Kind) -> Result<f64, &'static str> {
     }
 }
 
+/// Parse source text of a decimal numeric literal to a number.
+///
+/// # Errors
+/// Returns an error if `s` is not a valid decimal literal.
 pub fn parse_float(s: &str) -> Result<f64, &'static str> {
     let s = if s.contains('_') { Cow::Owned(s.replace('_', "")) } else { Cow::Borrowed(s) };
     s.parse::<f64>().map_err(|_| "invalid float")
@@ -81,9 +89,14 @@ fn parse_hex(s: &str) -> f64 {
     result
 }
 
+/// Parse source text of a BigInt literal token of `kind`, without the trailing `n`.
+///
+/// # Errors
+/// Returns an error if `s` is not a valid literal of `kind`.
 pub fn parse_big_int(s: &str, kind: Kind) -> Result<BigInt, &'static str> {
+    let s = if s.contains('_') { Cow::Owned(s.replace('_', "")) } else { Cow::Borrowed(s) };
     let s = match kind {
-        Kind::Decimal => s,
+        Kind::Decimal => &s,
         Kind::Binary | Kind::Octal | Kind::Hex => &s[2..],
         _ => unreachable!(),
     };
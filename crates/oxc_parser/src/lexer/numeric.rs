@@ -12,7 +12,8 @@ impl<'a> Lexer<'a> {
             Some('x' | 'X') => self.read_non_decimal(Kind::Hex),
             Some('e' | 'E') => {
                 self.consume_char();
-                self.read_decimal_exponent()
+                let kind = self.read_decimal_exponent();
+                self.check_after_numeric_literal(kind)
             }
             Some('.') => {
                 self.consume_char();
@@ -42,22 +43,23 @@ impl<'a> Lexer<'a> {
     fn read_non_decimal(&mut self, kind: Kind) -> Kind {
         self.consume_char();
 
+        // Separator directly after prefix e.g. `0x_FF`
+        self.read_misplaced_numeric_separator(|c| kind.matches_number_char(c));
+
         if self.peek().is_some_and(|c| kind.matches_number_char(c)) {
             self.consume_char();
         } else {
-            self.unexpected_err();
+            // e.g. `0x`, `0b2`
+            let span = Span::new(self.token.start, self.offset());
+            self.error(diagnostics::missing_number_digits(kind.as_str(), span));
             return Kind::Undetermined;
         }
 
         while let Some(c) = self.peek() {
             match c {
                 '_' => {
-                    self.consume_char();
-                    if self.peek().is_some_and(|c| kind.matches_number_char(c)) {
-                        self.consume_char();
-                    } else {
-                        self.unexpected_err();
-                        return Kind::Undetermined;
+                    if !self.read_numeric_separator(|c| kind.matches_number_char(c)) {
+                        break;
                     }
                 }
                 c if kind.matches_number_char(c) => {
@@ -96,13 +98,18 @@ impl<'a> Lexer<'a> {
             // allow 08e1 and 09e1
             Some('e') if kind == Kind::Decimal => {
                 self.consume_char();
-                self.read_decimal_exponent()
+                let kind = self.read_decimal_exponent();
+                self.check_after_numeric_literal(kind)
             }
             _ => self.check_after_numeric_literal(kind),
         }
     }
 
+    /// Read exponent, after `e` or `E` has been consumed.
+    ///
+    /// Returns `Kind::Undetermined` and reports an error if exponent has no digits (e.g. `1e`, `1e+`).
     fn read_decimal_exponent(&mut self) -> Kind {
+        let start = self.offset() - 1;
         let kind = match self.peek() {
             Some('-') => {
                 self.consume_char();
@@ -114,30 +121,35 @@ impl<'a> Lexer<'a> {
             }
             _ => Kind::PositiveExponential,
         };
-        self.read_decimal_digits();
-        kind
+        if self.read_decimal_digits() {
+            kind
+        } else {
+            let span = Span::new(start, self.offset());
+            self.error(diagnostics::missing_number_digits("exponent", span));
+            Kind::Undetermined
+        }
     }
 
-    fn read_decimal_digits(&mut self) {
+    /// Read decimal digits. Returns `false` if there are none.
+    fn read_decimal_digits(&mut self) -> bool {
+        // Separator directly after exponent e.g. `1e_5`
+        self.read_misplaced_numeric_separator(|c| c.is_ascii_digit());
+
         if self.peek().is_some_and(|c| c.is_ascii_digit()) {
             self.consume_char();
         } else {
-            self.unexpected_err();
-            return;
+            return false;
         }
 
         self.read_decimal_digits_after_first_digit();
+        true
     }
 
     fn read_decimal_digits_after_first_digit(&mut self) {
         while let Some(c) = self.peek() {
             match c {
                 '_' => {
-                    self.consume_char();
-                    if self.peek().is_some_and(|c| c.is_ascii_digit()) {
-                        self.consume_char();
-                    } else {
-                        self.unexpected_err();
+                    if !self.read_numeric_separator(|c| c.is_ascii_digit()) {
                         return;
                     }
                 }
@@ -149,19 +161,33 @@ impl<'a> Lexer<'a> {
         }
     }
 
+    /// Caller must ensure next char is a digit.
     pub(super) fn decimal_literal_after_decimal_point(&mut self) -> Kind {
-        self.read_decimal_digits();
-        self.optional_exponent();
-        self.check_after_numeric_literal(Kind::Float)
+        let has_digits = self.read_decimal_digits();
+        debug_assert!(has_digits);
+        let kind = self.float_optional_exponent();
+        self.check_after_numeric_literal(kind)
     }
 
     fn decimal_literal_after_decimal_point_after_digits(&mut self) -> Kind {
         self.optional_decimal_digits();
-        self.optional_exponent();
-        self.check_after_numeric_literal(Kind::Float)
+        let kind = self.float_optional_exponent();
+        self.check_after_numeric_literal(kind)
+    }
+
+    /// Read optional exponent of a number containing a decimal point.
+    /// Returns `Kind::Float`, or `Kind::Undetermined` if exponent is malformed.
+    fn float_optional_exponent(&mut self) -> Kind {
+        match self.optional_exponent() {
+            Some(Kind::Undetermined) => Kind::Undetermined,
+            _ => Kind::Float,
+        }
     }
 
     fn optional_decimal_digits(&mut self) {
+        // Separator directly after decimal point e.g. `1._5`
+        self.read_misplaced_numeric_separator(|c| c.is_ascii_digit());
+
         if self.peek().is_some_and(|c| c.is_ascii_digit()) {
             self.consume_char();
         } else {
@@ -170,6 +196,43 @@ impl<'a> Lexer<'a> {
         self.read_decimal_digits_after_first_digit();
     }
 
+    /// Consume a numeric separator `_` which follows a digit.
+    ///
+    /// Reports an error if separator is repeated (`1__0`), or not followed by a digit (`1_`).
+    /// Returns `true` if a digit follows, and so reading digits should continue.
+    fn read_numeric_separator(&mut self, is_digit: impl Fn(char) -> bool) -> bool {
+        let start = self.offset();
+        self.consume_char();
+        if self.peek() == Some('_') {
+            while self.peek() == Some('_') {
+                self.consume_char();
+            }
+            let span = Span::new(start, self.offset());
+            self.error(diagnostics::consecutive_numeric_separators(span));
+            return self.peek().is_some_and(is_digit);
+        }
+
+        if self.peek().is_some_and(is_digit) {
+            true
+        } else {
+            self.error(diagnostics::numeric_separator_not_allowed(Span::new(start, self.offset())));
+            false
+        }
+    }
+
+    /// Consume a numeric separator `_` which does not follow a digit, if it's followed by a digit,
+    /// and report an error.
+    ///
+    /// e.g. `0x_FF`, `1._5`, `1e_5`.
+    /// Separator not followed by a digit is not consumed, as it may be start of an identifier.
+    fn read_misplaced_numeric_separator(&mut self, is_digit: impl Fn(char) -> bool) {
+        if self.peek() == Some('_') && self.peek2().is_some_and(is_digit) {
+            let start = self.offset();
+            self.consume_char();
+            self.error(diagnostics::numeric_separator_not_allowed(Span::new(start, self.offset())));
+        }
+    }
+
     fn optional_exponent(&mut self) -> Option<Kind> {
         if matches!(self.peek(), Some('e' | 'E')) {
             self.consume_char();
@@ -179,6 +242,31 @@ impl<'a> Lexer<'a> {
     }
 
     fn check_after_numeric_literal(&mut self, kind: Kind) -> Kind {
+        // 2nd decimal point e.g. `1.2.3`, `1..2`, `1e5.5`.
+        // Consume rest of the number, so it's reported as a single error.
+        // NB: `1.2.foo` is valid (member access on `1.2`).
+        if matches!(kind, Kind::Float | Kind::PositiveExponential | Kind::NegativeExponential)
+            && self.peek() == Some('.')
+            && self.peek2().is_some_and(|c| c.is_ascii_digit())
+        {
+            let start = self.offset();
+            while self.peek() == Some('.') && self.peek2().is_some_and(|c| c.is_ascii_digit()) {
+                self.consume_char();
+                self.read_decimal_digits_after_first_digit();
+            }
+            self.error(diagnostics::unexpected_decimal_point(Span::new(start, self.offset())));
+            return self.check_after_numeric_literal(Kind::Undetermined);
+        }
+
+        // BigInt `n` suffix is only valid on integers e.g. `1.5n`, `1e3n`
+        if self.peek() == Some('n')
+            && matches!(kind, Kind::Float | Kind::PositiveExponential | Kind::NegativeExponential)
+        {
+            self.consume_char();
+            self.error(diagnostics::invalid_big_int(Span::new(self.token.start, self.offset())));
+            return self.check_after_numeric_literal(Kind::Undetermined);
+        }
+
         let offset = self.offset();
         // The SourceCharacter immediately following a NumericLiteral must not be an IdentifierStart or DecimalDigit.
         let c = self.peek();
@@ -197,3 +285,173 @@ impl<'a> Lexer<'a> {
         Kind::Undetermined
     }
 }
+
+#[cfg(test)]
+mod test {
+    use oxc_allocator::Allocator;
+    use oxc_span::Span;
+
+    use crate::lexer::{
+        parse_big_int, parse_float, parse_int,
+        tester::{error_spans, kinds, lex},
+        Kind,
+    };
+
+    #[test]
+    fn big_int() {
+        let allocator = Allocator::default();
+        for (source, kind, value) in [
+            ("123n", Kind::Decimal, 123),
+            ("0n", Kind::Decimal, 0),
+            ("0xFFn", Kind::Hex, 255),
+            ("0XfFn", Kind::Hex, 255),
+            ("0o17n", Kind::Octal, 15),
+            ("0b101n", Kind::Binary, 5),
+            ("1_000n", Kind::Decimal, 1000),
+            ("0xF_Fn", Kind::Hex, 255),
+        ] {
+            let (tokens, lexer) = lex(&allocator, source);
+            assert_eq!(kinds(&tokens), [kind], "{source}");
+            assert_eq!(tokens[0].end as usize, source.len(), "{source}");
+            assert!(lexer.errors.is_empty(), "{source}");
+            let digits = source.strip_suffix('n').unwrap();
+            assert_eq!(parse_big_int(digits, kind), Ok(value.into()), "{source}");
+        }
+
+        // `n` suffix is only valid on integers
+        for source in
+            ["1.5n", "1.n", ".5n", "0.5n", "1e3n", "1e-3n", "1E+3n", "1.5e3n", "0e1n", "08e1n"]
+        {
+            let (tokens, lexer) = lex(&allocator, source);
+            assert_eq!(kinds(&tokens), [Kind::Undetermined], "{source}");
+            assert_eq!(lexer.errors.len(), 1, "{source}");
+            assert_eq!(lexer.errors[0].message, "Invalid BigInt literal", "{source}");
+            #[allow(clippy::cast_possible_truncation)]
+            let span = Span::new(0, source.len() as u32);
+            assert_eq!(error_spans(&lexer), [span], "{source}");
+        }
+
+        // Identifier chars after `n` are consumed as part of invalid literal
+        let (tokens, lexer) = lex(&allocator, "1.5nx + 1");
+        assert_eq!(kinds(&tokens), [Kind::Undetermined, Kind::Plus, Kind::Decimal]);
+        assert_eq!(error_spans(&lexer), [Span::new(0, 4), Span::new(4, 5)]);
+
+        // Exponent after `0` or legacy decimal must not be followed by identifier chars
+        for source in ["0e1x", "08e1x"] {
+            let (tokens, lexer) = lex(&allocator, source);
+            assert_eq!(kinds(&tokens), [Kind::Undetermined], "{source}");
+            assert_eq!(lexer.errors[0].message, "Invalid characters after number", "{source}");
+        }
+    }
+
+    #[test]
+    fn numeric_separators() {
+        let allocator = Allocator::default();
+        for (source, kind, value) in [
+            ("1_000_000", Kind::Decimal, 1_000_000.0),
+            ("0xFF_FF", Kind::Hex, 65535.0),
+            ("0o7_7", Kind::Octal, 63.0),
+            ("0b1_0_1", Kind::Binary, 5.0),
+            ("1_0.2_5", Kind::Float, 10.25),
+            ("1_0e1_0", Kind::PositiveExponential, 1e11),
+        ] {
+            let (tokens, lexer) = lex(&allocator, source);
+            assert_eq!(kinds(&tokens), [kind], "{source}");
+            assert!(lexer.errors.is_empty(), "{source}");
+            let parsed = match kind {
+                Kind::Float | Kind::PositiveExponential => parse_float(source),
+                _ => parse_int(source, kind),
+            };
+            assert_eq!(parsed, Ok(value), "{source}");
+        }
+
+        let (tokens, lexer) = lex(&allocator, "1_000n");
+        assert_eq!(kinds(&tokens), [Kind::Decimal]);
+        assert!(lexer.errors.is_empty());
+        assert_eq!(parse_big_int("1_000", Kind::Decimal), Ok(1000.into()));
+        // Misplaced separators are reported by lexer. Value is still available for recovery.
+        assert_eq!(parse_big_int("0b_1", Kind::Binary), Ok(1.into()));
+        assert_eq!(parse_big_int("0x0__F", Kind::Hex), Ok(15.into()));
+
+        // Leading underscore is an identifier
+        let (tokens, lexer) = lex(&allocator, "_1");
+        assert_eq!(kinds(&tokens), [Kind::Ident]);
+        assert!(lexer.errors.is_empty());
+
+        for (source, message, span) in [
+            ("1_", "Numeric separators are not allowed here", Span::new(1, 2)),
+            ("0x1_", "Numeric separators are not allowed here", Span::new(3, 4)),
+            ("1_n", "Numeric separators are not allowed here", Span::new(1, 2)),
+            ("1__0", "Only one underscore is allowed as numeric separator", Span::new(1, 3)),
+            ("0b1___0", "Only one underscore is allowed as numeric separator", Span::new(3, 6)),
+            ("0x_FF", "Numeric separators are not allowed here", Span::new(2, 3)),
+            ("0o_7", "Numeric separators are not allowed here", Span::new(2, 3)),
+            ("1_.5", "Numeric separators are not allowed here", Span::new(1, 2)),
+            ("1._5", "Numeric separators are not allowed here", Span::new(2, 3)),
+            ("1e_5", "Numeric separators are not allowed here", Span::new(2, 3)),
+            ("1e-_5", "Numeric separators are not allowed here", Span::new(3, 4)),
+            ("1_e5", "Numeric separators are not allowed here", Span::new(1, 2)),
+        ] {
+            let (tokens, lexer) = lex(&allocator, source);
+            assert_eq!(tokens.len(), 1, "{source}");
+            assert_eq!(lexer.errors.len(), 1, "{source}");
+            assert_eq!(lexer.errors[0].message, message, "{source}");
+            assert_eq!(error_spans(&lexer), [span], "{source}");
+        }
+    }
+
+    #[test]
+    fn number_diagnostics() {
+        let allocator = Allocator::default();
+        for (source, message, span) in [
+            ("1e", "Missing exponent digits", Span::new(1, 2)),
+            ("1e+", "Missing exponent digits", Span::new(1, 3)),
+            ("1.5E-", "Missing exponent digits", Span::new(3, 5)),
+            ("0x", "Missing hex digits", Span::new(0, 2)),
+            ("0b", "Missing binary digits", Span::new(0, 2)),
+            ("0O", "Missing octal digits", Span::new(0, 2)),
+            ("1.2.3", "Unexpected decimal point in number", Span::new(3, 5)),
+            ("1e5.5", "Unexpected decimal point in number", Span::new(3, 5)),
+        ] {
+            let (tokens, lexer) = lex(&allocator, source);
+            assert_eq!(kinds(&tokens), [Kind::Undetermined], "{source}");
+            #[allow(clippy::cast_possible_truncation)]
+            let token_span = Span::new(0, source.len() as u32);
+            assert_eq!(tokens[0].span(), token_span, "{source}");
+            assert_eq!(lexer.errors.len(), 1, "{source}");
+            assert_eq!(lexer.errors[0].message, message, "{source}");
+            assert_eq!(error_spans(&lexer), [span], "{source}");
+        }
+
+        // Missing exponent followed by other tokens
+        let (tokens, lexer) = lex(&allocator, "1e + 2");
+        assert_eq!(kinds(&tokens), [Kind::Undetermined, Kind::Plus, Kind::Decimal]);
+        assert_eq!(error_spans(&lexer), [Span::new(1, 2)]);
+
+        // Valid
+        for (source, expected) in [
+            (".5", vec![Kind::Float]),
+            ("1.", vec![Kind::Float]),
+            (
+                "1..toString()",
+                vec![Kind::Float, Kind::Dot, Kind::Ident, Kind::LParen, Kind::RParen],
+            ),
+            (
+                "1.2.toString()",
+                vec![Kind::Float, Kind::Dot, Kind::Ident, Kind::LParen, Kind::RParen],
+            ),
+            ("1 .foo", vec![Kind::Decimal, Kind::Dot, Kind::Ident]),
+            (".e5", vec![Kind::Dot, Kind::Ident]),
+        ] {
+            let (tokens, lexer) = lex(&allocator, source);
+            assert_eq!(kinds(&tokens), expected, "{source}");
+            assert!(lexer.errors.is_empty(), "{source}");
+        }
+
+        // Identifier directly after decimal point
+        let (tokens, lexer) = lex(&allocator, "1.foo");
+        assert_eq!(kinds(&tokens), [Kind::Undetermined]);
+        assert_eq!(lexer.errors[0].message, "Invalid characters after number");
+        assert_eq!(error_spans(&lexer), [Span::new(2, 5)]);
+    }
+}
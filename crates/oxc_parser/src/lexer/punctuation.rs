@@ -3,7 +3,7 @@ use super::{Kind, Lexer, Token};
 impl<'a> Lexer<'a> {
     /// Section 12.8 Punctuators
     pub(super) fn read_dot(&mut self) -> Kind {
-        if self.peek() == Some('.') && self.peek2() == Some('.') {
+        if self.source.peek_byte_at(0) == Some(b'.') && self.source.peek_byte_at(1) == Some(b'.') {
             self.consume_char();
             self.consume_char();
             return Kind::Dot3;
@@ -30,6 +30,9 @@ impl<'a> Lexer<'a> {
             && self.source_type.is_script()
             && self.remaining().starts_with("!--")
         {
+            self.consume_char();
+            self.consume_char();
+            self.consume_char();
             None
         } else {
             Some(Kind::LAngle)
@@ -1,4 +1,4 @@
-use super::{Kind, Lexer, RegExpFlags, Token};
+use super::{Kind, Lexer, RegExpFlags, Span, Token};
 use crate::diagnostics;
 
 use oxc_syntax::identifier::is_line_terminator;
@@ -59,18 +59,132 @@ impl<'a> Lexer<'a> {
         let mut flags = RegExpFlags::empty();
 
         while let Some(ch @ ('$' | '_' | 'a'..='z' | 'A'..='Z' | '0'..='9')) = self.peek() {
+            let start = self.offset();
             self.consume_char();
+            let span = Span::new(start, self.offset());
             let Ok(flag) = RegExpFlags::try_from(ch) else {
-                self.error(diagnostics::reg_exp_flag(ch, self.current_offset()));
+                self.error(diagnostics::reg_exp_flag(ch, span));
                 continue;
             };
             if flags.contains(flag) {
-                self.error(diagnostics::reg_exp_flag_twice(ch, self.current_offset()));
+                self.error(diagnostics::reg_exp_flag_twice(ch, span));
                 continue;
             }
+            // `u` and `v` are mutually exclusive
+            let unicode_flags = RegExpFlags::U | RegExpFlags::V;
+            if unicode_flags.contains(flag) && flags.intersects(unicode_flags) {
+                self.error(diagnostics::reg_exp_flag_u_and_v(span));
+            }
             flags |= flag;
         }
 
         (pattern_end, flags)
     }
 }
+
+#[cfg(test)]
+mod test {
+    use oxc_allocator::Allocator;
+    use oxc_ast::ast::RegExpFlags;
+    use oxc_span::{SourceType, Span};
+
+    use crate::lexer::{tester::error_spans, Kind, Lexer};
+
+    #[test]
+    fn reg_exp_flags() {
+        let allocator = Allocator::default();
+        let lex_regex = |source| {
+            let mut lexer = Lexer::new_for_tests(&allocator, source, SourceType::default());
+            let token = lexer.next_token();
+            assert_eq!(token.kind, Kind::Slash);
+            let (_, _, flags) = lexer.next_regex(token.kind);
+            (flags, lexer)
+        };
+
+        let (flags, lexer) = lex_regex("/a/dgimsvy");
+        assert_eq!(flags, RegExpFlags::all() - RegExpFlags::U);
+        assert!(lexer.errors.is_empty());
+
+        let (flags, lexer) = lex_regex("/a/v");
+        assert_eq!(flags, RegExpFlags::V);
+        assert!(lexer.errors.is_empty());
+
+        let (flags, lexer) = lex_regex("/a/d");
+        assert_eq!(flags, RegExpFlags::D);
+        assert!(lexer.errors.is_empty());
+
+        let (flags, lexer) = lex_regex("/a/dgimsuy");
+        assert_eq!(flags, RegExpFlags::all() - RegExpFlags::V);
+        assert_eq!(flags.bits(), 0b0111_1111);
+        assert!(lexer.errors.is_empty());
+
+        // Flags are a set, so order in source doesn't matter.
+        // `Display` prints them in canonical order.
+        for source in ["/a/dgimsuy", "/a/yusmigd", "/a/gdyimus"] {
+            let (flags, lexer) = lex_regex(source);
+            assert_eq!(flags, RegExpFlags::all() - RegExpFlags::V, "{source}");
+            assert_eq!(flags.to_string(), "dgimsuy", "{source}");
+            assert!(lexer.errors.is_empty(), "{source}");
+        }
+        let (flags, _) = lex_regex("/a/yvd");
+        assert_eq!(flags.to_string(), "dvy");
+
+        // Lone `/` is an unterminated regexp
+        let (flags, lexer) = lex_regex("/");
+        assert_eq!(flags, RegExpFlags::empty());
+        assert_eq!(lexer.errors.len(), 1);
+        assert_eq!(lexer.errors[0].message, "Unterminated regular expression");
+
+        for (source, message, span) in [
+            (
+                "/a/uv",
+                "The 'u' and 'v' regular expression flags cannot be enabled at the same time",
+                Span::new(4, 5),
+            ),
+            (
+                "/a/vgu",
+                "The 'u' and 'v' regular expression flags cannot be enabled at the same time",
+                Span::new(5, 6),
+            ),
+            ("/a/gig", "Flag g is mentioned twice in regular expression literal", Span::new(5, 6)),
+            ("/a/z", "Unexpected flag z in regular expression literal", Span::new(3, 4)),
+            ("/a/gZi", "Unexpected flag Z in regular expression literal", Span::new(4, 5)),
+        ] {
+            let (_, lexer) = lex_regex(source);
+            assert_eq!(lexer.errors.len(), 1, "{source}");
+            assert_eq!(lexer.errors[0].message, message, "{source}");
+            assert_eq!(error_spans(&lexer), [span], "{source}");
+        }
+    }
+
+    #[test]
+    fn reg_exp_character_class() {
+        let allocator = Allocator::default();
+
+        // `/` only ends regexp if unescaped and outside character class
+        for (source, pattern) in [
+            ("/[/]/g;", "[/]"),
+            ("/[a\\]b]/;", "[a\\]b]"),
+            ("/a\\/b/;", "a\\/b"),
+            ("/[\\]/]/;", "[\\]/]"),
+            ("/[]/]/;", "[]"),
+            ("/\\[/]/;", "\\["),
+        ] {
+            let mut lexer = Lexer::new_for_tests(&allocator, source, SourceType::default());
+            let token = lexer.next_token();
+            let (token, pattern_end, _) = lexer.next_regex(token.kind);
+            assert_eq!(token.kind, Kind::RegExp, "{source}");
+            assert_eq!(&source[1..pattern_end as usize], pattern, "{source}");
+            assert!(lexer.errors.is_empty(), "{source}");
+        }
+
+        // Unterminated character class
+        for source in ["/[abc", "/[abc/", "/[abc]", "/[a/]\n/"] {
+            let mut lexer = Lexer::new_for_tests(&allocator, source, SourceType::default());
+            let token = lexer.next_token();
+            lexer.next_regex(token.kind);
+            assert_eq!(lexer.errors.len(), 1, "{source}");
+            assert_eq!(lexer.errors[0].message, "Unterminated regular expression", "{source}");
+        }
+    }
+}
@@ -1,10 +1,15 @@
 #![allow(clippy::unnecessary_safety_comment)]
 
 use super::search::SEARCH_BATCH_SIZE;
-use crate::{UniquePromise, MAX_LEN};
+use crate::{is_overlong, UniquePromise};
+
+use oxc_span::{LineIndex, Span};
 
 use std::{marker::PhantomData, slice, str};
 
+/// Byte order mark.
+const BOM: char = '\u{FEFF}';
+
 /// `Source` holds the source text for the lexer, and provides APIs to read it.
 ///
 /// It provides a cursor which allows consuming source text either as `char`s, or as bytes.
@@ -67,6 +72,10 @@ pub(super) struct Source<'a> {
     end: *const u8,
     /// Pointer to current position in source string
     ptr: *const u8,
+    /// Memory address which corresponds to offset 0.
+    /// Same as `start`, unless `Source` was created with a base offset.
+    /// Stored as an address, rather than the base offset, so calculating offsets is no more costly.
+    offset_origin: usize,
     /// Memory address past which not enough bytes remaining in source to process a batch of
     /// `SEARCH_BATCH_SIZE` bytes in one go.
     /// Must be `usize`, not a pointer, as if source is very short, a pointer could be out of bounds.
@@ -76,16 +85,26 @@ pub(super) struct Source<'a> {
 }
 
 impl<'a> Source<'a> {
-    /// Create `Source` from `&str`.
+    /// Create `Source` from `&str`, with all offsets shifted by `base_offset` (usually 0).
+    ///
+    /// For lexing a snippet extracted from a larger document (e.g. an HTML `<script>` tag),
+    /// so that offsets refer to positions in the original document.
+    /// Only offsets are shifted. `Source`'s position is still within `source_text`.
     ///
     /// Requiring a `UniquePromise` to be provided guarantees only 1 `Source` can exist
     /// on a single thread at one time.
     #[allow(clippy::needless_pass_by_value)]
-    pub(super) fn new(mut source_text: &'a str, _unique: UniquePromise) -> Self {
-        // If source text exceeds size limit, substitute a short source text which will fail to parse.
+    pub(super) fn new(
+        mut source_text: &'a str,
+        mut base_offset: u32,
+        _unique: UniquePromise,
+    ) -> Self {
+        // If source text exceeds size limit, or end of source would be at an offset greater than
+        // `u32::MAX`, substitute a short source text which will fail to parse.
         // `Parser::parse` will convert error to `diagnostics::overlong_source()`.
-        if source_text.len() > MAX_LEN {
+        if is_overlong(source_text, base_offset) {
             source_text = "\0";
+            base_offset = 0;
         }
 
         let start = source_text.as_ptr();
@@ -100,7 +119,31 @@ impl<'a> Source<'a> {
         // will always test positive, and disable batch search.
         let end_for_batch_search_addr = (end as usize).saturating_sub(SEARCH_BATCH_SIZE);
 
-        Self { start, end, ptr: start, end_for_batch_search_addr, _marker: PhantomData }
+        let mut source = Self {
+            start,
+            end,
+            ptr: start,
+            offset_origin: (start as usize).wrapping_sub(base_offset as usize),
+            end_for_batch_search_addr,
+            _marker: PhantomData,
+        };
+        source.reset();
+        source
+    }
+
+    /// Move current position back to start of source.
+    ///
+    /// Skips a leading byte order mark. It is not part of the program, but still counts towards
+    /// offsets, so spans continue to refer to positions in the original source text.
+    /// A BOM which appears anywhere else in source is lexed as whitespace.
+    pub(super) fn reset(&mut self) {
+        self.ptr = if self.whole().starts_with(BOM) {
+            // SAFETY: Source starts with BOM, which is 3 bytes, so `start + 3` is in bounds,
+            // and on a UTF-8 character boundary
+            unsafe { self.start.add(BOM.len_utf8()) }
+        } else {
+            self.start
+        };
     }
 
     /// Get entire source text as `&str`.
@@ -111,12 +154,30 @@ impl<'a> Source<'a> {
         // `start` and `end` are by definition on UTF-8 char boundaries.
         unsafe {
             self.str_between_positions_unchecked(
-                SourcePosition::new(self.start),
-                SourcePosition::new(self.end),
+                SourcePosition::new(self.start, self),
+                SourcePosition::new(self.end, self),
             )
         }
     }
 
+    /// Get entire source text as bytes.
+    #[allow(dead_code)]
+    #[inline]
+    pub(super) fn as_bytes(&self) -> &'a [u8] {
+        self.whole().as_bytes()
+    }
+
+    /// Get byte at `offset`, or `None` if `offset` is out of bounds.
+    ///
+    /// `offset` is absolute (same as offsets in `Token`s and `Span`s), so includes base offset.
+    /// Byte may be part of a multi-byte UTF-8 character.
+    #[allow(dead_code)]
+    #[inline]
+    pub(super) fn byte_at(&self, offset: u32) -> Option<u8> {
+        let index = offset.checked_sub(self.base_offset())?;
+        self.as_bytes().get(index as usize).copied()
+    }
+
     /// Get remaining source text as `&str`.
     #[inline]
     pub(super) fn remaining(&self) -> &'a str {
@@ -125,12 +186,47 @@ impl<'a> Source<'a> {
         // `end` is pointer to end of original `&str`, so be definition a UTF-8 char boundary.
         unsafe {
             self.str_between_positions_unchecked(
-                SourcePosition::new(self.ptr),
-                SourcePosition::new(self.end),
+                SourcePosition::new(self.ptr, self),
+                SourcePosition::new(self.end, self),
             )
         }
     }
 
+    /// Get length of entire source text in bytes.
+    ///
+    /// If source text exceeded `MAX_LEN`, this is the length of the substitute source text.
+    #[allow(dead_code)]
+    #[inline]
+    pub(super) fn len(&self) -> usize {
+        self.end as usize - self.start as usize
+    }
+
+    /// Return whether source text is empty.
+    #[allow(dead_code)]
+    #[inline]
+    pub(super) fn is_empty(&self) -> bool {
+        self.start == self.end
+    }
+
+    /// Find first occurrence of `byte` in source, searching from current position to end of source.
+    ///
+    /// Returns offset of the byte relative to current position, or `None` if not found.
+    /// Uses `memchr`, which is SIMD-accelerated on most platforms.
+    ///
+    /// If `byte` is not ASCII, the offset returned may not be on a UTF-8 character boundary.
+    #[inline]
+    pub(super) fn find_byte(&self, byte: u8) -> Option<usize> {
+        memchr::memchr(byte, self.remaining().as_bytes())
+    }
+
+    /// Get number of bytes remaining in source, from current position to end.
+    ///
+    /// Current position can never be after end, so this cannot underflow.
+    #[inline]
+    pub(super) fn remaining_len(&self) -> usize {
+        self.end as usize - self.ptr as usize
+    }
+
     /// Return whether at end of source.
     #[inline]
     pub(super) fn is_eof(&self) -> bool {
@@ -161,7 +257,7 @@ impl<'a> Source<'a> {
     pub(super) fn position(&self) -> SourcePosition<'a> {
         // SAFETY: Creating a `SourcePosition` from current position of `Source` is always valid,
         // if caller has upheld safety conditions of other unsafe methods of this type.
-        unsafe { SourcePosition::new(self.ptr) }
+        unsafe { SourcePosition::new(self.ptr, self) }
     }
 
     /// Move current position.
@@ -175,6 +271,13 @@ impl<'a> Source<'a> {
         // This is sufficient to guarantee that any `SourcePosition` that parser/lexer holds must be
         // from this `Source`.
         // This guarantee is what allows this function to be safe.
+        // `Lexer`s created in tests and benchmarks do not have this guarantee, but the only way a
+        // `SourcePosition` from outside can reach a `Lexer` is `Lexer::rewind`, which uses
+        // `set_position_checked` instead.
+        // In debug builds, also check `pos` was created from this `Source`, to catch misuse
+        // in tests and benchmarks, which can circumvent the `UniquePromise` requirement.
+        #[cfg(debug_assertions)]
+        assert!(pos.source_start == self.start, "`SourcePosition` is from a different `Source`");
 
         // SAFETY: `SourcePosition::read`'s contract is upheld by:
         // * The preceding checks that `pos.ptr` >= `self.start` and < `self.end`.
@@ -191,6 +294,53 @@ impl<'a> Source<'a> {
         self.ptr = pos.ptr;
     }
 
+    /// Move current position, checking `pos` is a valid position in this `Source`.
+    ///
+    /// Unlike `set_position`, this does not rely on `pos` having been created from this `Source`,
+    /// so is used for positions which come from outside the lexer, in a `LexerCheckpoint`.
+    ///
+    /// # Panics
+    /// Panics if `pos` is out of bounds, or is not on a UTF-8 character boundary.
+    pub(super) fn set_position_checked(&mut self, pos: SourcePosition) {
+        let offset = pos.addr().wrapping_sub(self.start as usize);
+        assert!(
+            self.whole().is_char_boundary(offset),
+            "`SourcePosition` is not valid for `Source`"
+        );
+        // SAFETY: `is_char_boundary` returns `false` if `offset` is past end of source text,
+        // so `offset` is within bounds (or at the end), and on a UTF-8 character boundary
+        self.ptr = unsafe { self.start.add(offset) };
+    }
+
+    /// Get `SourcePosition` for `offset`.
+    ///
+    /// Returns `None` if `offset` is out of bounds, or is not on a UTF-8 character boundary.
+    /// An `offset` at end of source is valid.
+    pub(super) fn position_at_offset(&self, offset: u32) -> Option<SourcePosition<'a>> {
+        let offset = offset.checked_sub(self.base_offset())? as usize;
+        if !self.whole().is_char_boundary(offset) {
+            return None;
+        }
+        // SAFETY: `is_char_boundary` returns `false` if `offset` is past end of source text,
+        // so `offset` is within bounds (or at the end), and on a UTF-8 character boundary
+        Some(unsafe { SourcePosition::new(self.start.add(offset), self) })
+    }
+
+    /// Move current position to `offset`.
+    ///
+    /// `offset` is absolute (same as offsets in `Token`s and `Span`s), so includes base offset.
+    /// Intended for error recovery, where parser needs to jump to an arbitrary offset
+    /// e.g. the end of a `Span` it has already consumed.
+    /// Use `position_at_offset` instead if `offset` may not be valid.
+    ///
+    /// # Panics
+    /// Panics if `offset` is out of bounds, or is not on a UTF-8 character boundary.
+    #[allow(dead_code)]
+    pub(super) fn set_offset(&mut self, offset: u32) {
+        let pos = self.position_at_offset(offset).expect("Invalid offset");
+        self.set_position(pos);
+    }
+
     /// Advance `Source`'s cursor to end.
     #[inline]
     pub(super) fn advance_to_end(&mut self) {
@@ -215,7 +365,7 @@ impl<'a> Source<'a> {
     pub(super) unsafe fn str_from_pos_to_current_unchecked(&self, pos: SourcePosition) -> &'a str {
         // SAFETY: Caller guarantees `pos` is not after current position of `Source`.
         // `self.ptr` is always a valid `SourcePosition` due to invariants of `Source`.
-        self.str_between_positions_unchecked(pos, SourcePosition::new(self.ptr))
+        self.str_between_positions_unchecked(pos, SourcePosition::new(self.ptr, self))
     }
 
     /// Get string slice from current position of `Source` up to a `SourcePosition`, without checks.
@@ -229,7 +379,7 @@ impl<'a> Source<'a> {
     pub(super) unsafe fn str_from_current_to_pos_unchecked(&self, pos: SourcePosition) -> &'a str {
         // SAFETY: Caller guarantees `pos` is not before current position of `Source`.
         // `self.ptr` is always a valid `SourcePosition` due to invariants of `Source`.
-        self.str_between_positions_unchecked(SourcePosition::new(self.ptr), pos)
+        self.str_between_positions_unchecked(SourcePosition::new(self.ptr, self), pos)
     }
 
     /// Get string slice from a `SourcePosition` up to the end of `Source`.
@@ -238,7 +388,7 @@ impl<'a> Source<'a> {
         // SAFETY: Invariants of `SourcePosition` is that it cannot be after end of `Source`,
         // and always on a UTF-8 character boundary.
         // `self.end` is always a valid `SourcePosition` due to invariants of `Source`.
-        unsafe { self.str_between_positions_unchecked(pos, SourcePosition::new(self.end)) }
+        unsafe { self.str_between_positions_unchecked(pos, SourcePosition::new(self.end, self)) }
     }
 
     /// Get string slice of source between 2 `SourcePosition`s, without checks.
@@ -277,6 +427,35 @@ impl<'a> Source<'a> {
         std::str::from_utf8_unchecked(slice)
     }
 
+    /// Get string slice of source text between `start` and `end` offsets.
+    ///
+    /// # Panics
+    /// Panics if `start` or `end` are out of bounds, or not on UTF-8 character boundaries.
+    pub(super) fn str_between_offsets(&self, start: u32, end: u32) -> &'a str {
+        let base_offset = self.base_offset();
+        &self.whole()[(start - base_offset) as usize..(end - base_offset) as usize]
+    }
+
+    /// Get offsets of start of each line in source text, in ascending order.
+    ///
+    /// First entry is always start of source. Line breaks are `\n`, `\r\n`, lone `\r`,
+    /// U+2028 and U+2029. Scans the whole source, so result should be cached by the caller.
+    pub(super) fn compute_line_starts(&self) -> Box<[u32]> {
+        let base_offset = self.base_offset();
+        LineIndex::new(self.whole())
+            .line_starts()
+            .iter()
+            .map(|&start| start + base_offset)
+            .collect()
+    }
+
+    /// Get base offset. Offset of start of source text, usually 0.
+    #[allow(clippy::cast_possible_truncation)]
+    #[inline]
+    pub(super) fn base_offset(&self) -> u32 {
+        (self.start as usize).wrapping_sub(self.offset_origin) as u32
+    }
+
     /// Get current position in source, relative to start of source.
     #[allow(clippy::cast_possible_truncation)]
     #[inline]
@@ -284,12 +463,43 @@ impl<'a> Source<'a> {
         self.offset_of(self.position())
     }
 
+    /// Get offset of start of program.
+    ///
+    /// This is base offset (usually 0), unless source starts with a byte order mark,
+    /// in which case it's offset after the BOM.
+    #[allow(clippy::cast_possible_truncation)]
+    pub(super) fn program_start_offset(&self) -> u32 {
+        if self.whole().starts_with(BOM) {
+            self.base_offset() + BOM.len_utf8() as u32
+        } else {
+            self.base_offset()
+        }
+    }
+
     /// Get offset of `pos`.
     #[allow(clippy::cast_possible_truncation)]
     #[inline]
     pub(super) fn offset_of(&self, pos: SourcePosition) -> u32 {
-        // Cannot overflow `u32` because of `MAX_LEN` check in `Source::new`
-        (pos.addr() - self.start as usize) as u32
+        // Cannot overflow `u32` because of `is_overlong` check in `Source::new`
+        pos.addr().wrapping_sub(self.offset_origin) as u32
+    }
+
+    /// Check if current position is within `span`.
+    ///
+    /// Start of span is inclusive, end is exclusive.
+    #[allow(dead_code)]
+    #[inline]
+    pub(super) fn current_in_span(&self, span: Span) -> bool {
+        span.contains(self.offset())
+    }
+
+    /// Check if `pos` is within `span`.
+    ///
+    /// Start of span is inclusive, end is exclusive.
+    #[allow(dead_code)]
+    #[inline]
+    pub(super) fn offset_in_span(&self, pos: SourcePosition, span: Span) -> bool {
+        span.contains(self.offset_of(pos))
     }
 
     /// Move current position back by `n` bytes.
@@ -329,6 +539,29 @@ impl<'a> Source<'a> {
         self.ptr = new_pos.ptr;
     }
 
+    /// Move current position forward by `n` bytes.
+    ///
+    /// For skipping bytes which have already been peeked, without reading them again.
+    ///
+    /// # SAFETY
+    /// Caller must ensure that advancing by `n` bytes does not move current position past end
+    /// of source, and places it on a UTF-8 character boundary.
+    /// Both are guaranteed if next `n` bytes are known to be ASCII.
+    #[inline]
+    pub(super) unsafe fn advance(&mut self, n: usize) {
+        debug_assert!(
+            n <= self.remaining_len(),
+            "Cannot advance {n} bytes - only {} bytes remaining",
+            self.remaining_len()
+        );
+        let new_pos = self.position().add(n);
+        debug_assert!(
+            new_pos.ptr == self.end || !is_utf8_cont_byte(new_pos.read()),
+            "Offset is not on a UTF-8 character boundary"
+        );
+        self.ptr = new_pos.ptr;
+    }
+
     /// Get next char of source, and advance position to after it.
     #[inline]
     pub(super) fn next_char(&mut self) -> Option<char> {
@@ -481,6 +714,22 @@ impl<'a> Source<'a> {
         }
     }
 
+    /// Peek byte `n` bytes ahead of current position, without consuming it.
+    ///
+    /// `peek_byte_at(0)` is equivalent to `peek_byte()`. Returns `None` if that byte is past end of file.
+    ///
+    /// Byte may be part of a multi-byte UTF-8 character, so this is intended for checking for ASCII
+    /// characters only. A UTF-8 continuation byte never matches an ASCII byte.
+    #[inline]
+    pub(super) fn peek_byte_at(&self, n: usize) -> Option<u8> {
+        if n < self.remaining_len() {
+            // SAFETY: `ptr + n` is before `end`, so within bounds of source text
+            Some(unsafe { *self.ptr.add(n) })
+        } else {
+            None
+        }
+    }
+
     /// Peek next byte of source without consuming it, without EOF bounds-check.
     ///
     /// # SAFETY
@@ -505,6 +754,10 @@ impl<'a> Source<'a> {
 #[derive(Debug, Clone, Copy)]
 pub struct SourcePosition<'a> {
     ptr: *const u8,
+    /// Start of the `Source` which this `SourcePosition` was created from.
+    /// Only used for checking `SourcePosition` is not used with a different `Source`.
+    #[cfg(debug_assertions)]
+    source_start: *const u8,
     _marker: PhantomData<&'a u8>,
 }
 
@@ -518,8 +771,14 @@ impl<'a> SourcePosition<'a> {
     ///   or 1 byte after the end of the source text (i.e. positioned at EOF).
     /// * It must be positioned on a UTF-8 character boundary (or EOF).
     #[inline]
-    pub(super) unsafe fn new(ptr: *const u8) -> Self {
-        Self { ptr, _marker: PhantomData }
+    #[allow(unused_variables)]
+    pub(super) unsafe fn new(ptr: *const u8, source: &Source<'a>) -> Self {
+        Self {
+            ptr,
+            #[cfg(debug_assertions)]
+            source_start: source.start,
+            _marker: PhantomData,
+        }
     }
 
     /// Get memory address of `SourcePosition` as a `usize`.
@@ -538,7 +797,7 @@ impl<'a> SourcePosition<'a> {
     /// just not past it.
     #[inline]
     pub(super) unsafe fn add(self, n: usize) -> Self {
-        Self::new(self.ptr.add(n))
+        Self { ptr: self.ptr.add(n), ..self }
     }
 
     /// Create new `SourcePosition` which is `n` bytes before this one.
@@ -549,7 +808,7 @@ impl<'a> SourcePosition<'a> {
     /// of `Source` this `SourcePosition` was created from.
     #[inline]
     pub(super) unsafe fn sub(self, n: usize) -> Self {
-        Self::new(self.ptr.sub(n))
+        Self { ptr: self.ptr.sub(n), ..self }
     }
 
     /// Read byte from this `SourcePosition`.
@@ -607,3 +866,329 @@ const fn is_utf8_cont_byte(byte: u8) -> bool {
     // 0x80 - 0xBF are continuation bytes i.e. not 1st byte of a UTF-8 character sequence
     byte >= 0x80 && byte < 0xC0
 }
+
+#[cfg(test)]
+#[allow(clippy::cast_possible_truncation)]
+mod test {
+    use oxc_allocator::Allocator;
+    use oxc_span::{SourceType, Span};
+
+    use crate::{
+        lexer::{
+            tester::{kinds, lex},
+            Kind, Lexer,
+        },
+        UniquePromise,
+    };
+
+    #[test]
+    fn source_position_at_offset() {
+        let allocator = Allocator::default();
+        let source_text = "x = 'é';";
+        let mut lexer = Lexer::new_for_tests(&allocator, source_text, SourceType::default());
+        let source = &mut lexer.source;
+
+        // Out of bounds
+        assert!(source.position_at_offset(source_text.len() as u32 + 1).is_none());
+        assert!(source.position_at_offset(u32::MAX).is_none());
+        // Middle of `é`
+        assert!(source.position_at_offset(6).is_none());
+
+        // Valid offsets round-trip through `set_position`
+        for offset in [0, 5, 7, source_text.len() as u32] {
+            let pos = source.position_at_offset(offset).unwrap();
+            source.set_position(pos);
+            assert_eq!(source.offset(), offset);
+        }
+
+        source.set_offset(2);
+        assert_eq!(source.offset(), 2);
+        assert_eq!(lexer.next_token().kind, Kind::Eq);
+    }
+
+    #[test]
+    #[allow(unsafe_code)]
+    fn source_in_span() {
+        let allocator = Allocator::default();
+        let mut lexer = Lexer::new_for_tests(&allocator, "foo bar", SourceType::default());
+        let foo = lexer.next_token().span();
+        assert_eq!(foo, Span::new(0, 3));
+
+        // Current position is at end of `foo`, which is exclusive
+        assert!(!lexer.source.current_in_span(foo));
+        assert!(lexer.source.current_in_span(Span::new(2, 4)));
+
+        let after_foo = lexer.source.position();
+        let bar = lexer.next_token().span();
+        let end = lexer.source.position();
+        assert!(!lexer.source.offset_in_span(after_foo, bar));
+        assert!(!lexer.source.offset_in_span(end, bar));
+        // SAFETY: `end` is at end of source, which is 7 bytes long
+        let (bar_start, bar_last) = unsafe { (end.sub(3), end.sub(1)) };
+        assert!(lexer.source.offset_in_span(bar_start, bar));
+        assert!(lexer.source.offset_in_span(bar_last, bar));
+    }
+
+    #[test]
+    #[allow(unsafe_code)]
+    fn source_advance() {
+        let allocator = Allocator::default();
+        let mut lexer = Lexer::new_for_tests(&allocator, "a >>>= é", SourceType::default());
+        assert_eq!(lexer.next_token().kind, Kind::Ident);
+        lexer.source.set_offset(2);
+        // SAFETY: Next 4 bytes are ASCII
+        unsafe { lexer.source.advance(4) };
+        assert_eq!(lexer.source.offset(), 6);
+        assert_eq!(lexer.source.remaining(), " é");
+
+        // SAFETY: Next byte is ASCII
+        unsafe { lexer.source.advance(1) };
+        // SAFETY: Next 2 bytes are a complete char, and advancing to end of source is valid
+        unsafe { lexer.source.advance(2) };
+        assert!(lexer.source.is_eof());
+        assert_eq!(lexer.source.remaining(), "");
+        // SAFETY: Advancing by 0 bytes is a no-op
+        unsafe { lexer.source.advance(0) };
+        assert_eq!(lexer.source.offset(), 9);
+    }
+
+    #[test]
+    #[allow(unsafe_code)]
+    fn source_str_between_positions() {
+        let allocator = Allocator::default();
+        let text = "a é 😀 b";
+        let mut lexer = Lexer::new_for_tests(&allocator, text, SourceType::default());
+        let boundaries = (0..=text.len()).filter(|&i| text.is_char_boundary(i)).map(|i| i as u32);
+
+        for start in boundaries.clone() {
+            for end in boundaries.clone().filter(|&end| end >= start) {
+                let start_pos = lexer.source.position_at_offset(start).unwrap();
+                let end_pos = lexer.source.position_at_offset(end).unwrap();
+                let expected = &text[start as usize..end as usize];
+                // SAFETY: `start` is not after `end`
+                let s = unsafe { lexer.source.str_between_positions_unchecked(start_pos, end_pos) };
+                assert_eq!(s, expected);
+                assert_eq!(lexer.source.str_between_offsets(start, end), expected);
+
+                lexer.source.set_position(end_pos);
+                assert_eq!(lexer.source.str_from_pos_to_current(start_pos), expected);
+                lexer.source.set_position(start_pos);
+                // SAFETY: `end_pos` is not before current position
+                let s = unsafe { lexer.source.str_from_current_to_pos_unchecked(end_pos) };
+                assert_eq!(s, expected);
+            }
+        }
+    }
+
+    #[test]
+    #[cfg(debug_assertions)]
+    #[allow(unsafe_code)]
+    #[should_panic(expected = "start.ptr <= end.ptr")]
+    fn source_str_between_positions_reversed() {
+        let allocator = Allocator::default();
+        let lexer = Lexer::new_for_tests(&allocator, "foo", SourceType::default());
+        let start = lexer.source.position_at_offset(2).unwrap();
+        let end = lexer.source.position_at_offset(1).unwrap();
+        // SAFETY: Not safe, but panics in debug mode before creating an invalid slice
+        unsafe { lexer.source.str_between_positions_unchecked(start, end) };
+    }
+
+    #[test]
+    #[cfg(debug_assertions)]
+    #[allow(unsafe_code)]
+    #[should_panic(expected = "Offset is not on a UTF-8 character boundary")]
+    fn source_advance_mid_char() {
+        let allocator = Allocator::default();
+        let mut lexer = Lexer::new_for_tests(&allocator, "'é'", SourceType::default());
+        // SAFETY: Not safe, but panics in debug mode before violating `Source`'s invariants
+        unsafe { lexer.source.advance(2) };
+    }
+
+    #[test]
+    #[cfg(debug_assertions)]
+    #[allow(unsafe_code)]
+    #[should_panic(expected = "Cannot advance 4 bytes - only 3 bytes remaining")]
+    fn source_advance_past_end() {
+        let allocator = Allocator::default();
+        let mut lexer = Lexer::new_for_tests(&allocator, "foo", SourceType::default());
+        // SAFETY: Not safe, but panics in debug mode before violating `Source`'s invariants
+        unsafe { lexer.source.advance(4) };
+    }
+
+    #[test]
+    #[should_panic(expected = "Invalid offset")]
+    fn source_set_offset_mid_char() {
+        let allocator = Allocator::default();
+        let mut lexer = Lexer::new_for_tests(&allocator, "'é'", SourceType::default());
+        lexer.source.set_offset(2);
+    }
+
+    #[test]
+    #[should_panic(expected = "Invalid offset")]
+    fn source_set_offset_out_of_bounds() {
+        let allocator = Allocator::default();
+        let mut lexer = Lexer::new_for_tests(&allocator, "foo", SourceType::default());
+        lexer.source.set_offset(4);
+    }
+
+    #[test]
+    #[cfg(debug_assertions)]
+    #[should_panic(expected = "`SourcePosition` is from a different `Source`")]
+    fn source_set_position_from_different_source() {
+        let allocator = Allocator::default();
+        // `Source`s overlap, and position is within bounds of both, so only the check that
+        // `SourcePosition` belongs to the `Source` can catch this
+        let source_text = "foo bar";
+        let mut lexer1 = Lexer::new_for_tests(&allocator, source_text, SourceType::default());
+        let mut lexer2 = Lexer::new_for_tests(&allocator, &source_text[4..], SourceType::default());
+        lexer1.source.set_offset(5);
+        lexer2.source.set_position(lexer1.source.position());
+    }
+
+    #[test]
+    fn source_find_byte() {
+        let allocator = Allocator::default();
+        let source_text = "'abc' + \"é\"";
+        let mut lexer = Lexer::new_for_tests(&allocator, source_text, SourceType::default());
+        let source = &mut lexer.source;
+        assert_eq!(source.find_byte(b'\''), Some(0));
+        assert_eq!(source.find_byte(b'+'), Some(6));
+        assert_eq!(source.find_byte(b'"'), Some(8));
+        assert_eq!(source.find_byte(b'x'), None);
+
+        // Search is from current position
+        source.next_char();
+        assert_eq!(source.find_byte(b'\''), Some(3));
+        assert_eq!(source.find_byte(b'a'), Some(0));
+        source.set_offset(4);
+        assert_eq!(source.find_byte(b'a'), None);
+
+        // Search does not go past end of source
+        let mut lexer = Lexer::new_for_tests(&allocator, &source_text[..4], SourceType::default());
+        assert_eq!(lexer.source.find_byte(b'\''), Some(0));
+        lexer.source.next_char();
+        assert_eq!(lexer.source.find_byte(b'\''), None);
+        lexer.source.advance_to_end();
+        assert_eq!(lexer.source.find_byte(b'c'), None);
+    }
+
+    #[test]
+    fn source_len() {
+        let allocator = Allocator::default();
+        for (source_text, len) in [("", 0), ("x", 1), ("é𝓍", 6), ("\u{feff}x", 4)] {
+            let mut lexer = Lexer::new_for_tests(&allocator, source_text, SourceType::default());
+            let source = &mut lexer.source;
+            assert_eq!(source.len(), len);
+            assert_eq!(source.len(), source.whole().len());
+            assert_eq!(source.is_empty(), len == 0);
+            // Length is unaffected by current position
+            source.advance_to_end();
+            assert_eq!(source.len(), len);
+        }
+    }
+
+    #[test]
+    fn source_as_bytes_and_byte_at() {
+        let allocator = Allocator::default();
+        let mut lexer = Lexer::new_for_tests(&allocator, "a é", SourceType::default());
+        let source = &mut lexer.source;
+        assert_eq!(source.as_bytes(), source.whole().as_bytes());
+        assert_eq!(source.as_bytes(), b"a \xC3\xA9");
+        assert_eq!(source.byte_at(0), Some(b'a'));
+        assert_eq!(source.byte_at(2), Some(0xC3));
+        assert_eq!(source.byte_at(3), Some(0xA9));
+        assert_eq!(source.byte_at(4), None);
+        assert_eq!(source.byte_at(u32::MAX), None);
+
+        // Not affected by current position
+        source.next_char();
+        assert_eq!(source.as_bytes(), b"a \xC3\xA9");
+        assert_eq!(source.byte_at(0), Some(b'a'));
+
+        // Offsets include base offset
+        let lexer = Lexer::new_with_offset(
+            &allocator,
+            "xy",
+            SourceType::default(),
+            100,
+            UniquePromise::new_for_tests(),
+        );
+        assert_eq!(lexer.source.as_bytes(), b"xy");
+        assert_eq!(lexer.source.byte_at(0), None);
+        assert_eq!(lexer.source.byte_at(99), None);
+        assert_eq!(lexer.source.byte_at(100), Some(b'x'));
+        assert_eq!(lexer.source.byte_at(101), Some(b'y'));
+        assert_eq!(lexer.source.byte_at(102), None);
+
+        let lexer = Lexer::new_for_tests(&allocator, "", SourceType::default());
+        assert!(lexer.source.as_bytes().is_empty());
+        assert_eq!(lexer.source.byte_at(0), None);
+    }
+
+    #[test]
+    fn source_peek_byte_at() {
+        let allocator = Allocator::default();
+        let mut lexer = Lexer::new_for_tests(&allocator, "a?.é", SourceType::default());
+        let source = &mut lexer.source;
+        assert_eq!(source.peek_byte_at(0), Some(b'a'));
+        assert_eq!(source.peek_byte_at(1), Some(b'?'));
+        assert_eq!(source.peek_byte_at(2), Some(b'.'));
+        // 2-byte char
+        assert_eq!(source.peek_byte_at(3), Some(0xC3));
+        assert_eq!(source.peek_byte_at(4), Some(0xA9));
+        assert_eq!(source.peek_byte_at(5), None);
+        assert_eq!(source.peek_byte_at(usize::MAX), None);
+
+        source.next_char();
+        source.next_char();
+        source.next_char();
+        assert_eq!(source.peek_byte_at(0), Some(0xC3));
+        assert_eq!(source.peek_byte_at(1), Some(0xA9));
+        assert_eq!(source.peek_byte_at(2), None);
+
+        source.next_char();
+        assert!(source.is_eof());
+        assert_eq!(source.peek_byte_at(0), None);
+        assert_eq!(source.peek_byte_at(1), None);
+        assert_eq!(source.peek_byte_at(2), None);
+
+        // Empty source
+        let lexer = Lexer::new_for_tests(&allocator, "", SourceType::default());
+        assert_eq!(lexer.source.peek_byte_at(0), None);
+        assert_eq!(lexer.source.peek_byte_at(1), None);
+
+        // Operators which use `peek_byte_at` for disambiguation
+        let (tokens, _) = lex(&allocator, "...a?.b?.1:..1");
+        assert_eq!(
+            kinds(&tokens),
+            [
+                Kind::Dot3,
+                Kind::Ident,
+                Kind::QuestionDot,
+                Kind::Ident,
+                Kind::Question,
+                Kind::Float,
+                Kind::Colon,
+                Kind::Dot,
+                Kind::Float
+            ]
+        );
+    }
+
+    #[test]
+    fn source_remaining_len() {
+        let allocator = Allocator::default();
+        let mut lexer = Lexer::new_for_tests(&allocator, "a é", SourceType::default());
+        let source = &mut lexer.source;
+        assert_eq!(source.remaining_len(), 4);
+        source.next_char();
+        assert_eq!(source.remaining_len(), 3);
+        source.next_char();
+        source.next_char();
+        assert_eq!(source.remaining_len(), 0);
+        assert!(source.is_eof());
+
+        let lexer = Lexer::new_for_tests(&allocator, "", SourceType::default());
+        assert_eq!(lexer.source.remaining_len(), 0);
+    }
+}
@@ -42,8 +42,8 @@ macro_rules! handle_string_literal {
             table: $table,
             start: after_opening_quote,
             handle_eof: {
-                $lexer.error(diagnostics::unterminated_string($lexer.unterminated_range()));
-                return Kind::Undetermined;
+                let value = $lexer.source.str_from_pos_to_current(after_opening_quote);
+                return $lexer.unterminated_string(value);
             },
         };
 
@@ -60,13 +60,10 @@ macro_rules! handle_string_literal {
                 handle_string_literal_escape!($lexer, $delimiter, $table, after_opening_quote)
             }),
             _ => {
-                // Line break. This is impossible in valid JS, so cold path.
-                cold_branch(|| {
-                    debug_assert!(matches!(next_byte, b'\r' | b'\n'));
-                    $lexer.consume_char();
-                    $lexer.error(diagnostics::unterminated_string($lexer.unterminated_range()));
-                    Kind::Undetermined
-                })
+                // Line break. This is impossible in valid JS (`unterminated_string` is `#[cold]`).
+                debug_assert!(matches!(next_byte, b'\r' | b'\n'));
+                let value = $lexer.source.str_from_pos_to_current(after_opening_quote);
+                $lexer.unterminated_string(value)
             }
         }
     }};
@@ -93,8 +90,10 @@ macro_rules! handle_string_literal_escape {
             let mut is_valid_escape_sequence = true;
             $lexer.read_string_escape_sequence(&mut str, false, &mut is_valid_escape_sequence);
             if !is_valid_escape_sequence {
+                // Span covers just the invalid escape. Substitute replacement char for recovery.
                 let range = Span::new(escape_start_offset, $lexer.offset());
                 $lexer.error(diagnostics::invalid_escape_sequence(range));
+                str.push(char::REPLACEMENT_CHARACTER);
             }
 
             // Consume bytes until reach end of string, line break, or another escape
@@ -128,20 +127,19 @@ macro_rules! handle_string_literal_escape {
                         continue 'outer;
                     }
                     _ => {
-                        // Line break. This is impossible in valid JS, so cold path.
-                        return cold_branch(|| {
-                            debug_assert!(matches!(b, b'\r' | b'\n'));
-                            $lexer.consume_char();
-                            $lexer.error(diagnostics::unterminated_string($lexer.unterminated_range()));
-                            Kind::Undetermined
-                        });
+                        // Line break. This is impossible in valid JS.
+                        debug_assert!(matches!(b, b'\r' | b'\n'));
+                        let chunk = $lexer.source.str_from_pos_to_current(chunk_start);
+                        str.push_str(chunk);
+                        return $lexer.unterminated_string(str.into_bump_str());
                     }
                 }
             }
 
             // EOF
-            $lexer.error(diagnostics::unterminated_string($lexer.unterminated_range()));
-            return Kind::Undetermined;
+            let chunk = $lexer.source.str_from_pos_to_current(chunk_start);
+            str.push_str(chunk);
+            return $lexer.unterminated_string(str.into_bump_str());
         }
 
         // Convert `str` to arena slice and save to `escaped_strings`
@@ -172,6 +170,18 @@ impl<'a> Lexer<'a> {
         unsafe { handle_string_literal!(self, b'\'', SINGLE_QUOTE_STRING_END_TABLE) }
     }
 
+    /// Handle an unterminated string literal, which ends at current position (EOF or a line break).
+    ///
+    /// Records an error, but still produces a `Kind::Str` token containing the string value so far,
+    /// so that parser can recover. Line break is not consumed, so next token is on a new line.
+    /// Value is always saved in `escaped_strings`, as token has no closing quote to trim.
+    #[cold]
+    fn unterminated_string(&mut self, value: &'a str) -> Kind {
+        self.error(diagnostics::unterminated_string(self.unterminated_range()));
+        self.save_string(true, value);
+        Kind::Str
+    }
+
     /// Save the string if it is escaped
     /// This reduces the overall memory consumption while keeping the `Token` size small
     /// Strings without escaped values can be retrieved as is from the token span
@@ -188,7 +198,7 @@ impl<'a> Lexer<'a> {
             return self.escaped_strings[&token.start];
         }
 
-        let raw = &self.source.whole()[token.start as usize..token.end as usize];
+        let raw = self.source.str_between_offsets(token.start, token.end);
         match token.kind {
             Kind::Str => {
                 &raw[1..raw.len() - 1] // omit surrounding quotes
@@ -200,3 +210,99 @@ impl<'a> Lexer<'a> {
         }
     }
 }
+
+#[cfg(test)]
+#[allow(clippy::cast_possible_truncation)]
+mod test {
+    use oxc_allocator::Allocator;
+    use oxc_span::Span;
+
+    use crate::lexer::{
+        tester::{error_spans, kinds, lex},
+        Kind,
+    };
+
+    #[test]
+    fn string_fast_path() {
+        let allocator = Allocator::default();
+        let long = "x".repeat(100);
+
+        // Strings without escapes are sliced directly from source, without allocating
+        for source in [
+            "'abc'".to_string(),
+            "\"abc\"".to_string(),
+            "''".to_string(),
+            "\"\"".to_string(),
+            "'a\"b'".to_string(),
+            "\"a'b\"".to_string(),
+            "'é𝓍'".to_string(),
+            format!("'{long}'"),
+        ] {
+            let (tokens, lexer) = lex(&allocator, &source);
+            assert_eq!(kinds(&tokens), [Kind::Str], "{source}");
+            assert!(!tokens[0].escaped, "{source}");
+            assert!(lexer.escaped_strings.is_empty(), "{source}");
+            assert_eq!(lexer.get_string(tokens[0]), &source[1..source.len() - 1], "{source}");
+            assert_eq!(tokens[0].end as usize, source.len(), "{source}");
+        }
+
+        // Fast path hands over to escape path on first `\`, at any position
+        // (including in 1st batch, and after 1 or more batches of `SEARCH_BATCH_SIZE` bytes)
+        for index in [0, 1, 31, 32, 33, 64, 99, 100] {
+            let (before, after) = long.split_at(index);
+            let source = format!("'{before}\\x61{after}' + 'b'");
+            let (tokens, lexer) = lex(&allocator, &source);
+            assert_eq!(kinds(&tokens), [Kind::Str, Kind::Plus, Kind::Str], "{index}");
+            assert!(tokens[0].escaped);
+            assert_eq!(lexer.get_string(tokens[0]), format!("{before}a{after}"), "{index}");
+            assert_eq!(tokens[0].end as usize, source.len() - 6, "{index}");
+            // Following string takes fast path again
+            assert!(!tokens[2].escaped);
+            assert_eq!(lexer.get_string(tokens[2]), "b");
+            assert!(lexer.errors.is_empty());
+        }
+
+        // Escaped quote
+        let (tokens, lexer) = lex(&allocator, "'a\\'b'");
+        assert_eq!(kinds(&tokens), [Kind::Str]);
+        assert_eq!(lexer.get_string(tokens[0]), "a'b");
+    }
+
+    #[test]
+    fn unterminated_string() {
+        let allocator = Allocator::default();
+
+        // EOF
+        for source in ["\"abc", "'abc", "\"a\\tc"] {
+            let (tokens, lexer) = lex(&allocator, source);
+            assert_eq!(tokens.len(), 1, "{source}");
+            let token = tokens[0];
+            assert_eq!(token.kind, Kind::Str, "{source}");
+            assert_eq!(token.span(), Span::new(0, source.len() as u32), "{source}");
+            let expected = if source.contains('\\') { "a\tc" } else { "abc" };
+            assert_eq!(lexer.get_string(token), expected, "{source}");
+            assert_eq!(error_spans(&lexer), [Span::new(0, source.len() as u32)], "{source}");
+        }
+
+        // Line terminator. Lexing continues on next line.
+        for source in ["\"abc\nfoo;", "'abc\r\nfoo;", "\"a\\tc\nfoo;"] {
+            let (tokens, lexer) = lex(&allocator, source);
+            assert_eq!(kinds(&tokens), [Kind::Str, Kind::Ident, Kind::Semicolon], "{source}");
+            let token = tokens[0];
+            assert_eq!(
+                token.span(),
+                Span::new(0, source.find(['\r', '\n']).unwrap() as u32),
+                "{source}"
+            );
+            let expected = if source.contains('\\') { "a\tc" } else { "abc" };
+            assert_eq!(lexer.get_string(token), expected, "{source}");
+            assert!(tokens[1].is_on_new_line, "{source}");
+            assert_eq!(tokens[1].source_text(source), "foo", "{source}");
+            assert_eq!(
+                error_spans(&lexer),
+                [Span::new(0, source.find(['\r', '\n']).unwrap() as u32)],
+                "{source}"
+            );
+        }
+    }
+}
@@ -95,7 +95,7 @@ impl<'a> Lexer<'a> {
     ) -> Kind {
         // Create arena string to hold modified template literal, containing up to before `\r`.
         // SAFETY: Caller guarantees `pos` is not before `self.source.position()`.
-        let str = self.template_literal_create_string(pos);
+        let mut str = self.template_literal_create_string(pos);
 
         // Skip `\r`.
         // SAFETY: Caller guarantees byte at `pos` is `\r`, so `pos + 1` is a UTF-8 char boundary.
@@ -115,10 +115,13 @@ impl<'a> Lexer<'a> {
 
         // If next char is `\n`, start next search after it.
         // `\n` is first char of next chunk, so it'll get added to `str` when chunk is pushed.
+        // Otherwise it's a lone `\r`, which is normalized to `\n`.
         // SAFETY: Have checked not at EOF.
         if pos.read() == b'\n' {
             // SAFETY: `\n` is ASCII, so advancing past it leaves `pos` on a UTF-8 char boundary
             pos = pos.add(1);
+        } else {
+            str.push('\n');
         }
 
         self.template_literal_escaped(str, pos, chunk_start, true, substitute, tail)
@@ -254,8 +257,11 @@ impl<'a> Lexer<'a> {
                                 // by 1 here, so that in total we skip 2 bytes for `\r\n`.
                                 // No need to push `\n` to `str`, as it's 1st char of next chunk,
                                 // and will be added to `str` when next chunk is pushed.
+                                // Otherwise it's a lone `\r`, which is normalized to `\n`.
                                 if chunk_start.read() == b'\n' {
                                     pos = chunk_start;
+                                } else {
+                                    str.push('\n');
                                 }
                             } else {
                                 // This is last byte in file. Continue to `handle_eof`.
@@ -320,12 +326,34 @@ impl<'a> Lexer<'a> {
         self.token.escaped = true;
     }
 
-    pub(crate) fn get_template_string(&self, token: Token) -> Option<&'a str> {
+    /// Get cooked value of a template literal component.
+    ///
+    /// Returns `None` if the template contains an invalid escape sequence.
+    /// This is legal in tagged templates, where the cooked value is `undefined`.
+    pub(crate) fn template_cooked(&self, token: Token) -> Option<&'a str> {
         if token.escaped {
             return self.escaped_templates[&token.start];
         }
-        let raw = &self.source.whole()[token.start as usize..token.end as usize];
-        Some(match token.kind {
+        Some(self.template_text(token))
+    }
+
+    /// Get raw value of a template literal component.
+    ///
+    /// Raw value is always available, even if the template contains an invalid escape sequence.
+    /// Line breaks `\r\n` and `\r` are normalized to `\n`, as per spec.
+    pub(crate) fn template_raw(&self, token: Token) -> &'a str {
+        let raw = self.template_text(token);
+        if raw.contains('\r') {
+            self.allocator.alloc_str(&raw.replace("\r\n", "\n").replace('\r', "\n"))
+        } else {
+            raw
+        }
+    }
+
+    /// Get source text of a template literal component, excluding its delimiters.
+    fn template_text(&self, token: Token) -> &'a str {
+        let raw = self.source.str_between_offsets(token.start, token.end);
+        match token.kind {
             Kind::NoSubstitutionTemplate | Kind::TemplateTail => {
                 &raw[1..raw.len() - 1] // omit surrounding quotes or leading "}" and trailing "`"
             }
@@ -333,6 +361,211 @@ impl<'a> Lexer<'a> {
                 &raw[1..raw.len() - 2] // omit leading "`" or "}" and trailing "${"
             }
             _ => raw,
-        })
+        }
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use oxc_allocator::Allocator;
+    use oxc_span::SourceType;
+
+    use crate::lexer::{
+        tester::{kinds, lex},
+        Kind, Lexer,
+    };
+
+    #[test]
+    fn template_substitutions() {
+        // Lex `source`, re-lexing `}` which closes a template substitution, as parser does.
+        // Returns kind, source text and cooked value of each token.
+        fn lex_templates<'a>(
+            allocator: &'a Allocator,
+            source: &'a str,
+        ) -> Vec<(Kind, &'a str, Option<&'a str>)> {
+            let mut lexer = Lexer::new_for_tests(allocator, source, SourceType::default());
+            // `true` for each open template substitution, `false` for each open `{`
+            let mut braces = vec![];
+            let mut tokens = vec![];
+            loop {
+                let mut token = lexer.next_token();
+                match token.kind {
+                    Kind::Eof => break,
+                    Kind::LCurly => braces.push(false),
+                    Kind::RCurly => {
+                        if braces.pop() == Some(true) {
+                            token = lexer.next_template_substitution_tail();
+                        }
+                    }
+                    _ => {}
+                }
+                if matches!(token.kind, Kind::TemplateHead | Kind::TemplateMiddle) {
+                    braces.push(true);
+                }
+                let cooked = if matches!(
+                    token.kind,
+                    Kind::NoSubstitutionTemplate
+                        | Kind::TemplateHead
+                        | Kind::TemplateMiddle
+                        | Kind::TemplateTail
+                ) {
+                    lexer.template_cooked(token)
+                } else {
+                    None
+                };
+                tokens.push((token.kind, token.source_text(source), cooked));
+            }
+            assert!(lexer.errors.is_empty(), "{source}");
+            assert!(braces.is_empty(), "{source}");
+            tokens
+        }
+
+        let allocator = Allocator::default();
+        assert_eq!(
+            lex_templates(&allocator, "`abc`"),
+            [(Kind::NoSubstitutionTemplate, "`abc`", Some("abc"))]
+        );
+        assert_eq!(
+            lex_templates(&allocator, "`a${b}c`"),
+            [
+                (Kind::TemplateHead, "`a${", Some("a")),
+                (Kind::Ident, "b", None),
+                (Kind::TemplateTail, "}c`", Some("c")),
+            ]
+        );
+        assert_eq!(
+            lex_templates(&allocator, "`${a}${b}`"),
+            [
+                (Kind::TemplateHead, "`${", Some("")),
+                (Kind::Ident, "a", None),
+                (Kind::TemplateMiddle, "}${", Some("")),
+                (Kind::Ident, "b", None),
+                (Kind::TemplateTail, "}`", Some("")),
+            ]
+        );
+        // Nested templates
+        assert_eq!(
+            lex_templates(&allocator, "`a${`b${c}d`}e`"),
+            [
+                (Kind::TemplateHead, "`a${", Some("a")),
+                (Kind::TemplateHead, "`b${", Some("b")),
+                (Kind::Ident, "c", None),
+                (Kind::TemplateTail, "}d`", Some("d")),
+                (Kind::TemplateTail, "}e`", Some("e")),
+            ]
+        );
+        assert_eq!(
+            lex_templates(&allocator, "`a${`b`}c${`${d}`}e`"),
+            [
+                (Kind::TemplateHead, "`a${", Some("a")),
+                (Kind::NoSubstitutionTemplate, "`b`", Some("b")),
+                (Kind::TemplateMiddle, "}c${", Some("c")),
+                (Kind::TemplateHead, "`${", Some("")),
+                (Kind::Ident, "d", None),
+                (Kind::TemplateTail, "}`", Some("")),
+                (Kind::TemplateTail, "}e`", Some("e")),
+            ]
+        );
+        // Braces inside substitution
+        assert_eq!(
+            lex_templates(&allocator, "`a${{b}}c`"),
+            [
+                (Kind::TemplateHead, "`a${", Some("a")),
+                (Kind::LCurly, "{", None),
+                (Kind::Ident, "b", None),
+                (Kind::RCurly, "}", None),
+                (Kind::TemplateTail, "}c`", Some("c")),
+            ]
+        );
+        // Escapes in each part
+        assert_eq!(
+            lex_templates(&allocator, "`\\x61${b}\\u0063${d}\\n`"),
+            [
+                (Kind::TemplateHead, "`\\x61${", Some("a")),
+                (Kind::Ident, "b", None),
+                (Kind::TemplateMiddle, "}\\u0063${", Some("c")),
+                (Kind::Ident, "d", None),
+                (Kind::TemplateTail, "}\\n`", Some("\n")),
+            ]
+        );
+    }
+
+    #[test]
+    fn template_cooked_and_raw() {
+        let allocator = Allocator::default();
+        let lex_template = |source| {
+            let mut lexer = Lexer::new_for_tests(&allocator, source, SourceType::default());
+            let token = lexer.next_token();
+            assert_eq!(token.kind, Kind::NoSubstitutionTemplate);
+            (lexer.template_cooked(token), lexer.template_raw(token))
+        };
+
+        assert_eq!(lex_template("`abc`"), (Some("abc"), "abc"));
+        assert_eq!(lex_template("`a\\nb`"), (Some("a\nb"), "a\\nb"));
+
+        // Invalid escape has no cooked value, but raw value is still available
+        // (e.g. for `String.raw`)
+        assert_eq!(lex_template("`\\u{}`"), (None, "\\u{}"));
+        assert_eq!(lex_template("`\\xZ\\u{}`"), (None, "\\xZ\\u{}"));
+
+        // Line breaks are normalized in both cooked and raw values
+        assert_eq!(lex_template("`a\r\nb`"), (Some("a\nb"), "a\nb"));
+        assert_eq!(lex_template("`\\u{}\r\n`"), (None, "\\u{}\n"));
+
+        // Template head and tail
+        let mut lexer = Lexer::new_for_tests(&allocator, "`a\\x${b}\\u`", SourceType::default());
+        let head = lexer.next_token();
+        assert_eq!(head.kind, Kind::TemplateHead);
+        assert_eq!((lexer.template_cooked(head), lexer.template_raw(head)), (None, "a\\x"));
+        assert_eq!(lexer.next_token().kind, Kind::Ident);
+        assert_eq!(lexer.next_token().kind, Kind::RCurly);
+        let tail = lexer.next_template_substitution_tail();
+        assert_eq!(tail.kind, Kind::TemplateTail);
+        assert_eq!((lexer.template_cooked(tail), lexer.template_raw(tail)), (None, "\\u"));
+    }
+
+    #[test]
+    fn template_line_break_normalization() {
+        let allocator = Allocator::default();
+
+        // `\r\n` and lone `\r` both become `\n`, whether found before or after an escape
+        for (source, cooked, raw) in [
+            ("`a\rb`", "a\nb", "a\nb"),
+            ("`\r`", "\n", "\n"),
+            ("`\r\n`", "\n", "\n"),
+            ("`a\r\r\nb\n\rc`", "a\n\nb\n\nc", "a\n\nb\n\nc"),
+            ("`\\x41\rb\r\nc`", "A\nb\nc", "\\x41\nb\nc"),
+            ("`a\r\\x41\r`", "a\nA\n", "a\n\\x41\n"),
+            ("`\\\r\nx`", "x", "\\\nx"),
+        ] {
+            let (tokens, lexer) = lex(&allocator, source);
+            assert_eq!(kinds(&tokens), [Kind::NoSubstitutionTemplate], "{source:?}");
+            assert_eq!(lexer.template_cooked(tokens[0]), Some(cooked), "{source:?}");
+            assert_eq!(lexer.template_raw(tokens[0]), raw, "{source:?}");
+            // Source text of token is not altered
+            assert_eq!(tokens[0].source_text(source), source);
+            assert!(lexer.errors.is_empty());
+        }
+
+        // Template head, middle and tail
+        let source = "`a\r${x}b\r\n${y}c\r`";
+        let mut lexer = Lexer::new_for_tests(&allocator, source, SourceType::default());
+        let head = lexer.next_token();
+        assert_eq!(head.kind, Kind::TemplateHead);
+        assert_eq!((lexer.template_cooked(head), lexer.template_raw(head)), (Some("a\n"), "a\n"));
+        lexer.next_token();
+        lexer.next_token();
+        let middle = lexer.next_template_substitution_tail();
+        assert_eq!(middle.kind, Kind::TemplateMiddle);
+        assert_eq!(
+            (lexer.template_cooked(middle), lexer.template_raw(middle)),
+            (Some("b\n"), "b\n")
+        );
+        lexer.next_token();
+        lexer.next_token();
+        let tail = lexer.next_template_substitution_tail();
+        assert_eq!(tail.kind, Kind::TemplateTail);
+        assert_eq!((lexer.template_cooked(tail), lexer.template_raw(tail)), (Some("c\n"), "c\n"));
+        assert!(lexer.errors.is_empty());
     }
 }
@@ -0,0 +1,44 @@
+//! Helpers for lexer tests
+
+use oxc_allocator::Allocator;
+use oxc_span::{SourceType, Span};
+
+use super::{Kind, Lexer, Token};
+
+/// Lex `source` to end, returning all tokens (excluding `Eof`) and the `Lexer`.
+pub(super) fn lex<'a>(allocator: &'a Allocator, source: &'a str) -> (Vec<Token>, Lexer<'a>) {
+    lex_as(allocator, source, SourceType::default())
+}
+
+/// Same as [`lex`], with a specific `SourceType`.
+pub(super) fn lex_as<'a>(
+    allocator: &'a Allocator,
+    source: &'a str,
+    source_type: SourceType,
+) -> (Vec<Token>, Lexer<'a>) {
+    let mut lexer = Lexer::new_for_tests(allocator, source, source_type);
+    let tokens = lex_to_end(&mut lexer);
+    (tokens, lexer)
+}
+
+/// Lex remaining tokens of `lexer` (excluding `Eof`).
+pub(super) fn lex_to_end(lexer: &mut Lexer) -> Vec<Token> {
+    lexer.tokens().take_while(|token| token.kind != Kind::Eof).collect()
+}
+
+pub(super) fn kinds(tokens: &[Token]) -> Vec<Kind> {
+    tokens.iter().map(|token| token.kind).collect()
+}
+
+/// Get span of first label of each error
+#[allow(clippy::cast_possible_truncation)]
+pub(super) fn error_spans(lexer: &Lexer) -> Vec<Span> {
+    lexer
+        .errors
+        .iter()
+        .map(|error| {
+            let label = &error.labels.as_ref().unwrap()[0];
+            Span::new(label.offset() as u32, (label.offset() + label.len()) as u32)
+        })
+        .collect()
+}
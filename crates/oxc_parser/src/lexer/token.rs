@@ -4,7 +4,11 @@ use oxc_span::Span;
 
 use super::kind::Kind;
 
-#[derive(Debug, Clone, Copy, Default)]
+/// Tokens are equal if they have same `kind`, `start`, `end`, `is_on_new_line` and `escaped`.
+///
+/// Cooked values of escaped tokens are stored in the `Lexer`, not in the `Token`,
+/// so 2 escaped tokens from different sources may compare equal even if their cooked values differ.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Hash)]
 pub struct Token {
     /// Token Kind
     pub kind: Kind,
@@ -29,6 +33,7 @@ pub struct Token {
     // Padding to fill to 16 bytes.
     // This makes copying a `Token` 1 x xmmword load & store, rather than 1 x dword + 1 x qword
     // and `Token::default()` is 1 x xmmword store, rather than 1 x dword + 1 x qword.
+    // Padding is always zero, so does not affect equality or hashing.
     _padding1: u8,
     _padding2: u32,
 }
@@ -47,7 +52,120 @@ impl Token {
         Span::new(self.start, self.end)
     }
 
-    pub fn escaped(&self) -> bool {
+    /// Length of token in bytes
+    #[allow(dead_code)]
+    pub fn len(&self) -> u32 {
+        self.end - self.start
+    }
+
+    /// Returns `true` if token has zero length (e.g. [`Kind::Eof`])
+    #[allow(dead_code)]
+    pub fn is_empty(&self) -> bool {
+        self.start == self.end
+    }
+
+    pub fn is_escaped(&self) -> bool {
         self.escaped
     }
+
+    /// Get source text of this token.
+    ///
+    /// `source_text` must be the source text which the token was lexed from.
+    ///
+    /// This is the raw text of token as written, including quotes, and any escapes unprocessed.
+    /// For escaped tokens, the cooked value is stored in the `Lexer`
+    /// (see [`Token::escaped`]).
+    ///
+    /// # Panics
+    /// Panics if token is out of bounds of `source_text`, or does not fall on UTF-8 char boundaries.
+    #[allow(dead_code)]
+    pub fn source_text<'a>(&self, source_text: &'a str) -> &'a str {
+        self.span().source_text(source_text)
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use oxc_allocator::Allocator;
+    use oxc_span::{SourceType, Span};
+
+    use crate::lexer::{tester::lex, Kind, Lexer};
+
+    #[test]
+    fn token_accessors() {
+        let allocator = Allocator::default();
+        let source = "let \\u0061b = 'str' + 123n **= `t`; // c";
+        let (tokens, _) = lex(&allocator, source);
+        let expected = [
+            (Kind::Let, "let"),
+            (Kind::Ident, "\\u0061b"),
+            (Kind::Eq, "="),
+            (Kind::Str, "'str'"),
+            (Kind::Plus, "+"),
+            (Kind::Decimal, "123n"),
+            (Kind::Star2Eq, "**="),
+            (Kind::NoSubstitutionTemplate, "`t`"),
+            (Kind::Semicolon, ";"),
+        ];
+        assert_eq!(tokens.len(), expected.len());
+        for (token, (kind, text)) in tokens.iter().zip(expected) {
+            assert_eq!(token.kind, kind);
+            assert_eq!(token.span(), Span::new(token.start, token.end));
+            assert_eq!(token.len(), token.end - token.start);
+            assert_eq!(token.len() as usize, text.len());
+            assert!(!token.is_empty());
+            assert_eq!(token.source_text(source), text);
+            assert_eq!(token.is_escaped(), token.escaped);
+        }
+        assert!(tokens[1].is_escaped());
+        assert!(!tokens[0].is_escaped());
+
+        // Source text of escaped tokens is raw, cooked value is obtained from `Lexer`
+        let source = "'a\\nb' `c\\u{64}`";
+        let (tokens, lexer) = lex(&allocator, source);
+        assert_eq!(tokens[0].source_text(source), "'a\\nb'");
+        assert_eq!(lexer.get_string(tokens[0]), "a\nb");
+        assert_eq!(tokens[1].source_text(source), "`c\\u{64}`");
+        assert_eq!(lexer.template_cooked(tokens[1]), Some("cd"));
+
+        let mut lexer = Lexer::new_for_tests(&allocator, "", SourceType::default());
+        let eof = lexer.next_token();
+        assert_eq!(eof.kind, Kind::Eof);
+        assert_eq!(eof.len(), 0);
+        assert!(eof.is_empty());
+        assert_eq!(eof.source_text(""), "");
+    }
+
+    #[test]
+    fn token_eq_and_hash() {
+        use rustc_hash::FxHashSet;
+
+        let allocator = Allocator::default();
+        let (tokens, _) = lex(&allocator, "a = b;\nc");
+        let (tokens2, _) = lex(&allocator, "a = b;\nc");
+        assert_eq!(tokens, tokens2);
+
+        // Differing in kind, span or line break
+        let (tokens3, _) = lex(&allocator, "a = b;c");
+        assert_ne!(tokens[4], tokens3[4]);
+        let (tokens4, _) = lex(&allocator, "a == b;\nc");
+        assert_ne!(tokens[1], tokens4[1]);
+        assert_ne!(tokens[2], tokens4[2]);
+        assert_eq!(tokens[0], tokens4[0]);
+
+        // Escaped tokens with same span compare equal, even if cooked values differ
+        let (escaped, lexer) = lex(&allocator, "'\\x61'");
+        let (escaped2, lexer2) = lex(&allocator, "'\\x62'");
+        assert_eq!(escaped, escaped2);
+        assert_ne!(lexer.get_string(escaped[0]), lexer2.get_string(escaped2[0]));
+        // But not equal to unescaped token with same span
+        let (unescaped, _) = lex(&allocator, "'abcd'");
+        assert_ne!(escaped, unescaped);
+
+        let set = tokens.iter().chain(&tokens2).chain(&tokens3).copied().collect::<FxHashSet<_>>();
+        // `tokens2` are all duplicates of `tokens`. `tokens3` shares all but last token (`c`).
+        assert_eq!(set.len(), tokens.len() + 1);
+        assert!(set.contains(&tokens4[0]));
+        assert!(!set.contains(&tokens4[1]));
+    }
 }
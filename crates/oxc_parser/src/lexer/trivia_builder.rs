@@ -3,7 +3,7 @@ use oxc_span::Span;
 
 #[derive(Debug, Default)]
 pub struct TriviaBuilder {
-    // Duplicated comments can be added from rewind, use `BTreeMap` to ensure uniqueness
+    // Duplicated comments can be added from rewind, `add_comment` ensures uniqueness
     comments: Vec<(u32, Comment)>,
     irregular_whitespaces: Vec<Span>,
 }
@@ -14,12 +14,44 @@ impl TriviaBuilder {
         Trivias::new(comments, self.irregular_whitespaces)
     }
 
-    pub fn add_single_line_comment(&mut self, start: u32, end: u32) {
-        // skip leading `//`
-        self.add_comment(start + 2, Comment::new(end, CommentKind::SingleLine));
+    /// Iterate over comments found so far, in source order.
+    ///
+    /// Spans exclude the comment delimiters (`//`, `/*` and `*/`).
+    #[allow(dead_code)]
+    pub fn comments(&self) -> impl Iterator<Item = (Span, CommentKind)> + '_ {
+        self.comments.iter().map(|(start, comment)| (Span::new(*start, comment.end), comment.kind))
     }
 
-    pub fn add_multi_line_comment(&mut self, start: u32, end: u32) {
+    /// Get spans of irregular whitespace found so far, in source order.
+    #[allow(dead_code)]
+    pub fn irregular_whitespaces(&self) -> &[Span] {
+        &self.irregular_whitespaces
+    }
+
+    /// Get number of comments and irregular whitespaces found so far, to pass to `rewind`.
+    pub(crate) fn checkpoint(&self) -> (usize, usize) {
+        (self.comments.len(), self.irregular_whitespaces.len())
+    }
+
+    /// Remove trivia found since `checkpoint` was created.
+    pub(crate) fn rewind(&mut self, checkpoint: (usize, usize)) {
+        self.comments.truncate(checkpoint.0);
+        self.irregular_whitespaces.truncate(checkpoint.1);
+    }
+
+    /// Remove all trivia, retaining allocated capacity.
+    #[allow(dead_code)]
+    pub(crate) fn clear(&mut self) {
+        self.comments.clear();
+        self.irregular_whitespaces.clear();
+    }
+
+    /// `start` is start of comment content, after leading `//` (or `<!--` / `-->` in scripts)
+    pub(crate) fn add_single_line_comment(&mut self, start: u32, end: u32) {
+        self.add_comment(start, Comment::new(end, CommentKind::SingleLine));
+    }
+
+    pub(crate) fn add_multi_line_comment(&mut self, start: u32, end: u32) {
         // skip leading `/*` and trailing `*/`
         self.add_comment(start + 2, Comment::new(end - 2, CommentKind::MultiLine));
     }
@@ -35,7 +67,66 @@ impl TriviaBuilder {
         self.comments.push((start, comment));
     }
 
-    pub fn add_irregular_whitespace(&mut self, start: u32, end: u32) {
+    pub(crate) fn add_irregular_whitespace(&mut self, start: u32, end: u32) {
+        // Same as comments, skip whitespace which was already added before a rewind
+        if let Some(span) = self.irregular_whitespaces.last() {
+            if start <= span.start {
+                return;
+            }
+        }
         self.irregular_whitespaces.push(Span::new(start, end));
     }
 }
+
+#[cfg(test)]
+mod test {
+    use oxc_allocator::Allocator;
+    use oxc_ast::CommentKind;
+    use oxc_span::{SourceType, Span};
+
+    use crate::lexer::{tester::lex, Kind, Lexer};
+
+    #[test]
+    fn trivia() {
+        let allocator = Allocator::default();
+        let source = "// leading\nfoo(/* inline */ a);\u{a0}// trailing\n/* block\n */";
+        let (_, lexer) = lex(&allocator, source);
+        let comments = lexer.trivia().comments().collect::<Vec<_>>();
+        assert_eq!(
+            comments,
+            [
+                (Span::new(2, 10), CommentKind::SingleLine),
+                (Span::new(17, 25), CommentKind::MultiLine),
+                (Span::new(35, 44), CommentKind::SingleLine),
+                (Span::new(47, 55), CommentKind::MultiLine),
+            ]
+        );
+        let texts = comments.iter().map(|(span, _)| span.source_text(source)).collect::<Vec<_>>();
+        assert_eq!(texts, [" leading", " inline ", " trailing", " block\n "]);
+        assert_eq!(lexer.trivia().irregular_whitespaces(), &[Span::new(31, 33)]);
+    }
+
+    #[test]
+    fn trivia_not_duplicated_on_rewind() {
+        let allocator = Allocator::default();
+        let source = "a /* 1 */\u{a0}b // 2\nc";
+        let mut lexer = Lexer::new_for_tests(&allocator, source, SourceType::default());
+        assert_eq!(lexer.next_token().kind, Kind::Ident);
+        let checkpoint = lexer.checkpoint();
+        assert_eq!(lexer.lookahead(2).kind, Kind::Ident);
+        assert_eq!(lexer.next_token().kind, Kind::Ident);
+        assert_eq!(lexer.next_token().kind, Kind::Ident);
+        lexer.rewind(checkpoint);
+        while lexer.next_token().kind != Kind::Eof {}
+
+        let comments = lexer.trivia().comments().collect::<Vec<_>>();
+        assert_eq!(
+            comments,
+            [
+                (Span::new(4, 7), CommentKind::MultiLine),
+                (Span::new(15, 17), CommentKind::SingleLine)
+            ]
+        );
+        assert_eq!(lexer.trivia().irregular_whitespaces(), &[Span::new(9, 11)]);
+    }
+}
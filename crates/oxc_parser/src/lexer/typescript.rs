@@ -15,11 +15,12 @@ impl<'a> Lexer<'a> {
         self.finish_next(kind)
     }
 
-    /// Re-tokenize '>>' and '>>>' to '>'
+    /// Re-tokenize '>>', '>>>', '>=', '>>=' or '>>>=' to '>'
     pub(crate) fn re_lex_as_typescript_r_angle(&mut self, kind: Kind) -> Token {
         let offset = match kind {
-            Kind::ShiftRight => 2,
-            Kind::ShiftRight3 => 3,
+            Kind::ShiftRight | Kind::GtEq => 2,
+            Kind::ShiftRight3 | Kind::ShiftRightEq => 3,
+            Kind::ShiftRight3Eq => 4,
             _ => unreachable!(),
         };
         self.token.start = self.offset() - offset;
@@ -29,3 +30,40 @@ impl<'a> Lexer<'a> {
         self.finish_next(kind)
     }
 }
+
+#[cfg(test)]
+mod test {
+    use oxc_allocator::Allocator;
+    use oxc_span::{SourceType, Span};
+
+    use crate::lexer::{Kind, Lexer};
+
+    #[test]
+    fn re_lex_as_typescript_r_angle() {
+        let allocator = Allocator::default();
+        for (source, kind, next_kind) in [
+            ("x>>", Kind::ShiftRight, Kind::RAngle),
+            ("x>>>", Kind::ShiftRight3, Kind::RAngle),
+            ("x>=", Kind::GtEq, Kind::Eq),
+            ("x>>=", Kind::ShiftRightEq, Kind::RAngle),
+            ("x>>>=", Kind::ShiftRight3Eq, Kind::RAngle),
+        ] {
+            let mut lexer = Lexer::new_for_tests(&allocator, source, SourceType::default());
+            assert_eq!(lexer.next_token().kind, Kind::Ident);
+            // Lexer produces a single `>`, which parser then extends to a longer token
+            assert_eq!(lexer.next_token().kind, Kind::RAngle);
+            let token = lexer.next_right_angle();
+            assert_eq!(token.kind, kind, "{source}");
+            assert_eq!(token.end as usize, source.len(), "{source}");
+
+            let token = lexer.re_lex_as_typescript_r_angle(token.kind);
+            assert_eq!(token.kind, Kind::RAngle, "{source}");
+            assert_eq!(token.span(), Span::new(1, 2), "{source}");
+
+            // Lexing continues after the first `>`
+            let token = lexer.next_token();
+            assert_eq!(token.kind, next_kind, "{source}");
+            assert_eq!(token.start, 2, "{source}");
+        }
+    }
+}
@@ -39,8 +39,7 @@ impl<'a> Lexer<'a> {
             }
             _ => {
                 self.consume_char();
-                self.error(diagnostics::invalid_character(c, self.unterminated_range()));
-                Kind::Undetermined
+                self.invalid_characters(c)
             }
         }
     }
@@ -144,11 +143,12 @@ impl<'a> Lexer<'a> {
         if !self.next_eq('{') {
             return None;
         }
-        let value = self.code_point()?;
+        // Consume closing `}` even if code point is out of range, so error span covers whole escape
+        let value = self.code_point();
         if !self.next_eq('}') {
             return None;
         }
-        Some(SurrogatePair::CodePoint(value))
+        value.map(SurrogatePair::CodePoint)
     }
 
     fn hex_4_digits(&mut self) -> Option<u32> {
@@ -170,15 +170,17 @@ impl<'a> Lexer<'a> {
         Some(value)
     }
 
+    /// Consumes all hex digits, even after value is out of range.
     fn code_point(&mut self) -> Option<u32> {
         let mut value = self.hex_digit()?;
+        let mut in_range = true;
         while let Some(next) = self.hex_digit() {
-            value = (value << 4) | next;
-            if value > 0x0010_FFFF {
-                return None;
+            if in_range {
+                value = (value << 4) | next;
+                in_range = value <= 0x0010_FFFF;
             }
         }
-        Some(value)
+        in_range.then_some(value)
     }
 
     /// Surrogate pairs
@@ -189,8 +191,8 @@ impl<'a> Lexer<'a> {
         let high = self.hex_4_digits()?;
         // The first code unit of a surrogate pair is always in the range from 0xD800 to 0xDBFF, and is called a high surrogate or a lead surrogate.
         let is_pair = (0xD800..=0xDBFF).contains(&high)
-            && self.peek() == Some('\\')
-            && self.peek2() == Some('u');
+            && self.source.peek_byte_at(0) == Some(b'\\')
+            && self.source.peek_byte_at(1) == Some(b'u');
         if !is_pair {
             return Some(SurrogatePair::CodePoint(high));
         }
@@ -315,3 +317,39 @@ impl<'a> Lexer<'a> {
         }
     }
 }
+
+#[cfg(test)]
+mod test {
+    use oxc_allocator::Allocator;
+    use oxc_span::Span;
+
+    use crate::lexer::{
+        tester::{error_spans, kinds, lex},
+        Kind,
+    };
+
+    #[test]
+    fn invalid_escapes() {
+        let allocator = Allocator::default();
+
+        // Error span covers only the invalid escape. Replacement char substituted in value.
+        for (source, value, span) in [
+            ("'a\\u{110000}b'", "a\u{FFFD}b", Span::new(2, 12)),
+            ("'a\\u{FFFFFFFFF}b'", "a\u{FFFD}b", Span::new(2, 15)),
+            ("'a\\x1'", "a\u{FFFD}", Span::new(2, 5)),
+            ("'a\\uZZZZb'", "a\u{FFFD}ZZZZb", Span::new(2, 4)),
+            ("'a\\u12'", "a\u{FFFD}", Span::new(2, 6)),
+            ("'\\u{}'", "\u{FFFD}", Span::new(1, 5)),
+        ] {
+            let (tokens, lexer) = lex(&allocator, source);
+            assert_eq!(kinds(&tokens), [Kind::Str], "{source}");
+            assert_eq!(lexer.get_string(tokens[0]), value, "{source}");
+            assert_eq!(error_spans(&lexer), [span], "{source}");
+        }
+
+        // Lone surrogates are valid in strings
+        let (tokens, lexer) = lex(&allocator, "'\\uD800' '\\u{D800}'");
+        assert_eq!(kinds(&tokens), [Kind::Str, Kind::Str]);
+        assert!(lexer.errors.is_empty());
+    }
+}
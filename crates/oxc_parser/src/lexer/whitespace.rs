@@ -23,3 +23,44 @@ impl<'a> Lexer<'a> {
         Kind::Skip
     }
 }
+
+#[cfg(test)]
+mod test {
+    use oxc_allocator::Allocator;
+    use oxc_span::Span;
+
+    use crate::lexer::{
+        tester::{kinds, lex},
+        Kind,
+    };
+
+    #[test]
+    fn unicode_whitespace() {
+        let allocator = Allocator::default();
+        let whitespace = [
+            '\u{b}', '\u{c}', '\u{a0}', '\u{1680}', '\u{2000}', '\u{2001}', '\u{2002}', '\u{2003}',
+            '\u{2004}', '\u{2005}', '\u{2006}', '\u{2007}', '\u{2008}', '\u{2009}', '\u{200a}',
+            '\u{202f}', '\u{205f}', '\u{3000}', '\u{feff}',
+        ];
+        for c in whitespace {
+            #[allow(clippy::cast_possible_truncation)]
+            let len = c.len_utf8() as u32;
+
+            // Between tokens, at start of line, and repeated
+            for (source, spans) in [
+                (format!("a{c}b"), vec![Span::new(1, 1 + len)]),
+                (format!("a\n{c}b"), vec![Span::new(2, 2 + len)]),
+                (
+                    format!("a {c}{c} b"),
+                    vec![Span::new(2, 2 + len), Span::new(2 + len, 2 + len * 2)],
+                ),
+            ] {
+                let (tokens, lexer) = lex(&allocator, &source);
+                assert_eq!(kinds(&tokens), [Kind::Ident, Kind::Ident], "{c:?}");
+                assert_eq!(tokens[1].source_text(&source), "b", "{c:?}");
+                assert!(lexer.errors.is_empty(), "{c:?}");
+                assert_eq!(lexer.trivia().irregular_whitespaces(), spans, "{c:?}");
+            }
+        }
+    }
+}
@@ -88,7 +88,7 @@ use oxc_diagnostics::{OxcDiagnostic, Result};
 use oxc_span::{ModuleKind, SourceType, Span};
 
 use crate::{
-    lexer::{Lexer, Token},
+    lexer::{Lexer, LexerReturn, Token},
     state::ParserState,
 };
 
@@ -106,6 +106,13 @@ pub const MAX_LEN: usize = if std::mem::size_of::<usize>() >= 8 {
     isize::MAX as usize
 };
 
+/// Check if source text is too long to be parsed, when it starts at `base_offset`.
+///
+/// Length cannot exceed `MAX_LEN`, and end of source must be at an offset which fits in a `u32`.
+pub(crate) fn is_overlong(source_text: &str, base_offset: u32) -> bool {
+    source_text.len() > MAX_LEN.min((u32::MAX - base_offset) as usize)
+}
+
 /// Return value of parser consisting of AST, errors and comments
 ///
 /// The parser always return a valid AST.
@@ -118,6 +125,18 @@ pub struct ParserReturn<'a> {
     pub panicked: bool,
 }
 
+/// ECMAScript version which source is parsed as.
+///
+/// Controls which words are lexed as keywords. In sloppy mode code before ES2015,
+/// `let` and `yield` are plain identifiers, so e.g. `let` is lexed as `Kind::Ident`, not `Kind::Let`.
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq, PartialOrd, Ord)]
+pub enum EcmaVersion {
+    Es5,
+    Es2015,
+    #[default]
+    Latest,
+}
+
 /// Parser options
 #[derive(Clone, Copy)]
 struct ParserOptions {
@@ -130,11 +149,24 @@ struct ParserOptions {
     ///
     /// Default: true
     pub preserve_parens: bool,
+    /// ECMAScript version which source is parsed as.
+    ///
+    /// Default: `EcmaVersion::Latest`
+    pub ecma_version: EcmaVersion,
+    /// Offset of start of source text. Added to all spans.
+    ///
+    /// Default: 0
+    pub base_offset: u32,
 }
 
 impl Default for ParserOptions {
     fn default() -> Self {
-        Self { allow_return_outside_function: false, preserve_parens: true }
+        Self {
+            allow_return_outside_function: false,
+            preserve_parens: true,
+            ecma_version: EcmaVersion::Latest,
+            base_offset: 0,
+        }
     }
 }
 
@@ -174,6 +206,27 @@ impl<'a> Parser<'a> {
         self.options.preserve_parens = allow;
         self
     }
+
+    /// Set ECMAScript version which source is parsed as.
+    ///
+    /// For legacy code, where e.g. `let` is a valid identifier in ES5.
+    #[must_use]
+    pub fn ecma_version(mut self, ecma_version: EcmaVersion) -> Self {
+        self.options.ecma_version = ecma_version;
+        self
+    }
+
+    /// Set offset of start of source text.
+    ///
+    /// For parsing a snippet extracted from a larger document (e.g. an HTML `<script>` tag),
+    /// so that spans in AST, trivia and diagnostics refer to positions in the original document.
+    /// If end of source would be at an offset greater than `u32::MAX`, parsing fails,
+    /// same as for source text exceeding [`MAX_LEN`].
+    #[must_use]
+    pub fn base_offset(mut self, base_offset: u32) -> Self {
+        self.options.base_offset = base_offset;
+        self
+    }
 }
 
 mod parser_parse {
@@ -265,6 +318,10 @@ struct ParserImpl<'a> {
     /// Emit `ParenthesizedExpression` in AST.
     /// Default: `true`
     preserve_parens: bool,
+
+    /// Offset of start of source text.
+    /// Default: 0
+    base_offset: u32,
 }
 
 impl<'a> ParserImpl<'a> {
@@ -280,8 +337,16 @@ impl<'a> ParserImpl<'a> {
         options: ParserOptions,
         unique: UniquePromise,
     ) -> Self {
+        let mut lexer = Lexer::new_with_offset(
+            allocator,
+            source_text,
+            source_type,
+            options.base_offset,
+            unique,
+        );
+        lexer.set_ecma_version(options.ecma_version);
         Self {
-            lexer: Lexer::new(allocator, source_text, source_type, unique),
+            lexer,
             source_type,
             source_text,
             errors: vec![],
@@ -291,6 +356,7 @@ impl<'a> ParserImpl<'a> {
             ctx: Self::default_context(source_type, options),
             ast: AstBuilder::new(allocator),
             preserve_parens: options.preserve_parens,
+            base_offset: options.base_offset,
         }
     }
 
@@ -329,8 +395,8 @@ impl<'a> ParserImpl<'a> {
                 (program, true)
             }
         };
-        let errors = self.lexer.errors.into_iter().chain(self.errors).collect();
-        let trivias = self.lexer.trivia_builder.build();
+        let LexerReturn { errors: lexer_errors, trivias, .. } = self.lexer.finish();
+        let errors = lexer_errors.into_iter().chain(self.errors).collect();
         ParserReturn { program, errors, trivias, panicked }
     }
 
@@ -343,7 +409,7 @@ impl<'a> ParserImpl<'a> {
         let (directives, statements) =
             self.parse_directives_and_statements(/* is_top_level */ true)?;
 
-        let span = Span::new(0, self.source_text.len() as u32);
+        let span = Span::new(self.base_offset, self.base_offset + self.source_text.len() as u32);
         Ok(self.ast.program(span, self.source_type, directives, hashbang, statements))
     }
 
@@ -366,15 +432,18 @@ impl<'a> ParserImpl<'a> {
             && (self.source_text.starts_with("// @flow")
                 || self.source_text.starts_with("/* @flow */"))
         {
-            return Some(diagnostics::flow(Span::new(0, 8)));
+            // Offset from lexer, not `self.base_offset`, as lexer uses 0 if source is overlong
+            let base_offset = self.lexer.base_offset();
+            return Some(diagnostics::flow(Span::new(base_offset, base_offset + 8)));
         }
         None
     }
 
-    /// Check if source length exceeds MAX_LEN, if the file cannot be parsed.
+    /// Check if source length exceeds MAX_LEN (or end offset exceeds `u32::MAX`),
+    /// if the file cannot be parsed.
     /// Original parsing error is not real - `Lexer::new` substituted "\0" as the source text.
     fn overlong_error(&self) -> Option<OxcDiagnostic> {
-        if self.source_text.len() > MAX_LEN {
+        if is_overlong(self.source_text, self.base_offset) {
             return Some(diagnostics::overlong_source());
         }
         None
@@ -406,7 +475,11 @@ impl<'a> ParserImpl<'a> {
 
 #[cfg(test)]
 mod test {
-    use oxc_ast::CommentKind;
+    use oxc_ast::{
+        ast::{Expression, JSXElementName, Statement},
+        CommentKind,
+    };
+    use oxc_span::{Atom, GetSpan};
     use std::path::Path;
 
     use super::*;
@@ -461,6 +534,68 @@ mod test {
         }
     }
 
+    #[test]
+    fn unterminated_string_directive() {
+        let allocator = Allocator::default();
+        let source_type = SourceType::default();
+        for (source, directive) in [("'", ""), ("\"use strict", "use strict"), ("'a\n'b'", "a")] {
+            let ret = Parser::new(&allocator, source, source_type).parse();
+            assert_eq!(ret.errors.len(), 1, "{source}");
+            assert_eq!(ret.program.directives[0].directive, directive, "{source}");
+        }
+    }
+
+    #[test]
+    fn strict_mode() {
+        let allocator = Allocator::default();
+        let source_type = SourceType::default();
+        // In ES5, `let` is only a keyword in strict mode code. So `let\nx = 1` is one `let`
+        // declaration in code made strict by a `"use strict"` directive, and 2 statements otherwise.
+        for (source, statements) in [
+            ("let\nx = 1", 2),
+            ("'use strict'; let\nx = 1", 1),
+            ("function f() { 'use strict' } let\nx = 1", 3),
+            ("class A {} let\nx = 1", 3),
+        ] {
+            let ret =
+                Parser::new(&allocator, source, source_type).ecma_version(EcmaVersion::Es5).parse();
+            assert!(ret.errors.is_empty(), "{source}");
+            assert_eq!(ret.program.body.len(), statements, "{source}");
+        }
+    }
+
+    #[test]
+    #[allow(clippy::cast_possible_truncation)]
+    fn base_offset() {
+        let allocator = Allocator::default();
+        let source_type = SourceType::default().with_jsx(true);
+        let source = "#!node\n'use strict'; /a/g; 1n; <a-b />; 0777 // c";
+        let ret = Parser::new(&allocator, source, source_type).base_offset(100).parse();
+        assert!(!ret.panicked);
+        assert_eq!(ret.program.span, Span::new(100, 100 + source.len() as u32));
+        assert_eq!(ret.program.hashbang.as_ref().unwrap().value, "node\n");
+        assert_eq!(ret.program.directives[0].directive, "use strict");
+        assert_eq!(ret.program.body[0].span(), Span::new(121, 126));
+        let Statement::ExpressionStatement(stmt) = &ret.program.body[0] else { unreachable!() };
+        let Expression::RegExpLiteral(regexp) = &stmt.expression else { unreachable!() };
+        assert_eq!(regexp.regex.pattern, "a");
+        let Statement::ExpressionStatement(stmt) = &ret.program.body[2] else { unreachable!() };
+        let Expression::JSXElement(element) = &stmt.expression else { unreachable!() };
+        let name = &element.opening_element.name;
+        assert!(matches!(name, JSXElementName::Identifier(ident) if ident.name == "a-b"));
+        let comment_start = source.find("//").unwrap() as u32 + 100;
+        assert_eq!(
+            ret.trivias.comments().collect::<Vec<_>>(),
+            [(CommentKind::SingleLine, Span::new(comment_start + 2, comment_start + 4))]
+        );
+
+        // End of source beyond `u32::MAX`
+        let ret = Parser::new(&allocator, "x", source_type).base_offset(u32::MAX).parse();
+        assert!(ret.panicked);
+        assert_eq!(ret.errors.len(), 1);
+        assert_eq!(ret.errors[0].to_string(), "Source length exceeds 4 GiB limit");
+    }
+
     #[test]
     fn comments() {
         let allocator = Allocator::default();
@@ -474,6 +609,68 @@ mod test {
         }
     }
 
+    #[test]
+    fn tagged_template_raw() {
+        let allocator = Allocator::default();
+        let source_type = SourceType::default();
+        // Invalid escapes are legal in tagged templates. Cooked value is `undefined`, but raw remains.
+        let source = "String.raw`a\\u{}${x}b\\x\r\n`";
+        let ret = Parser::new(&allocator, source, source_type).parse();
+        assert!(ret.errors.is_empty());
+        let Some(Statement::ExpressionStatement(stmt)) = ret.program.body.first() else {
+            panic!("expected expression statement");
+        };
+        let Expression::TaggedTemplateExpression(expr) = &stmt.expression else {
+            panic!("expected tagged template");
+        };
+        let values = expr
+            .quasi
+            .quasis
+            .iter()
+            .map(|quasi| (quasi.value.raw.as_str(), quasi.value.cooked.as_ref().map(Atom::as_str)))
+            .collect::<Vec<_>>();
+        assert_eq!(values, [("a\\u{}", None), ("b\\x\n", None)]);
+    }
+
+    #[test]
+    fn ecma_version() {
+        let allocator = Allocator::default();
+        let source_type = SourceType::default();
+        let source = "var let = 1; let = 2; let\nx = 3;";
+
+        // `let` followed by newline and identifier is a `let` declaration in ES2015+
+        let ret = Parser::new(&allocator, source, source_type).parse();
+        assert!(ret.errors.is_empty());
+        assert!(matches!(ret.program.body[2], Statement::VariableDeclaration(_)));
+
+        // In ES5, `let` is an identifier, so ASI applies after it
+        let ret =
+            Parser::new(&allocator, source, source_type).ecma_version(EcmaVersion::Es5).parse();
+        assert!(ret.errors.is_empty());
+        assert_eq!(ret.program.body.len(), 4);
+        assert!(matches!(ret.program.body[2], Statement::ExpressionStatement(_)));
+    }
+
+    #[test]
+    fn using_declaration() {
+        let allocator = Allocator::default();
+        let source_type = SourceType::default().with_module(true);
+        let source = "using x = foo(); await using y = bar(); using = 1; using(z); using\nw = 2;\
+            await using\nv = 3;";
+        let ret = Parser::new(&allocator, source, source_type).parse();
+        assert!(ret.errors.is_empty(), "{:?}", ret.errors);
+        let is_await = ret
+            .program
+            .body
+            .iter()
+            .map(|stmt| match stmt {
+                Statement::UsingDeclaration(decl) => Some(decl.is_await),
+                _ => None,
+            })
+            .collect::<Vec<_>>();
+        assert_eq!(is_await, [Some(false), Some(true), None, None, None, None, None, None]);
+    }
+
     #[test]
     fn memory_leak() {
         let allocator = Allocator::default();
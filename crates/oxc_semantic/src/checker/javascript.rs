@@ -39,7 +39,6 @@ impl EarlyErrorJavaScript {
             AstKind::PrivateIdentifier(ident) => check_private_identifier_outside_class(ident, ctx),
             AstKind::NumericLiteral(lit) => check_number_literal(lit, ctx),
             AstKind::StringLiteral(lit) => check_string_literal(lit, ctx),
-            AstKind::RegExpLiteral(lit) => check_regexp_literal(lit, ctx),
 
             AstKind::Directive(dir) => check_directive(dir, ctx),
             AstKind::ModuleDeclaration(decl) => {
@@ -588,20 +587,6 @@ fn check_function_declaration<'a>(
     };
 }
 
-fn reg_exp_flag_u_and_v(span0: Span) -> OxcDiagnostic {
-    OxcDiagnostic::error(
-        "The 'u' and 'v' regular expression flags cannot be enabled at the same time",
-    )
-    .with_labels([span0.into()])
-}
-
-fn check_regexp_literal(lit: &RegExpLiteral, ctx: &SemanticBuilder<'_>) {
-    let flags = lit.regex.flags;
-    if flags.contains(RegExpFlags::U | RegExpFlags::V) {
-        ctx.error(reg_exp_flag_u_and_v(lit.span));
-    }
-}
-
 fn with_statement(span0: Span) -> OxcDiagnostic {
     OxcDiagnostic::error("'with' statements are not allowed").with_labels([span0.into()])
 }
@@ -1,9 +1,20 @@
-use std::{borrow::Borrow, fmt, hash, ops::Deref};
+use std::{
+    borrow::{Borrow, Cow},
+    cmp::Ordering,
+    fmt,
+    hash::{self, BuildHasher},
+    mem,
+    ops::Deref,
+    str::{self, Utf8Error},
+};
 
 #[cfg(feature = "serialize")]
 use serde::{Serialize, Serializer};
 
 use compact_str::CompactString;
+use oxc_allocator::Allocator;
+
+use crate::Keyword;
 
 #[cfg(feature = "serialize")]
 #[wasm_bindgen::prelude::wasm_bindgen(typescript_custom_section)]
@@ -13,7 +24,11 @@ export type CompactStr = string;
 "#;
 
 /// Maximum length for inline string, which can be created with `CompactStr::new_const`.
-pub const MAX_INLINE_LEN: usize = 16;
+///
+/// 16 bytes on 64-bit platforms. Less on 32-bit platforms, where `CompactStr` can only inline
+/// strings up to the size of a `String` (12 bytes).
+pub const MAX_INLINE_LEN: usize =
+    if mem::size_of::<String>() < 16 { mem::size_of::<String>() } else { 16 };
 
 /// An inlinable string for oxc_allocator.
 ///
@@ -25,11 +40,332 @@ pub const MAX_INLINE_LEN: usize = 16;
 pub struct Atom<'a>(&'a str);
 
 impl<'a> Atom<'a> {
+    /// Create an `Atom` from bytes, borrowing them without copying.
+    ///
+    /// # Errors
+    /// Returns `Err` if `bytes` are not valid UTF-8.
+    pub fn from_utf8(bytes: &'a [u8]) -> Result<Self, Utf8Error> {
+        str::from_utf8(bytes).map(Self)
+    }
+
+    /// Create an `Atom` from bytes, replacing any invalid UTF-8 sequences with U+FFFD.
+    ///
+    /// If `bytes` are valid UTF-8, they are borrowed without copying.
+    /// Otherwise the converted string is copied into the arena.
+    pub fn from_utf8_lossy_in(bytes: &'a [u8], allocator: &'a Allocator) -> Self {
+        Self::from_cow_in(String::from_utf8_lossy(bytes), allocator)
+    }
+
+    /// Create an `Atom` by concatenating byte chunks into a single allocation in the arena.
+    ///
+    /// Useful where a string arrives in pieces (e.g. from a streaming reader). Chunks do not need
+    /// to be split on char boundaries - only the combined bytes must be valid UTF-8.
+    ///
+    /// Chunks are iterated twice - once to sum their lengths, and then to copy them into the arena.
+    ///
+    /// # Errors
+    /// Returns `Err` if the combined bytes are not valid UTF-8.
+    pub fn from_chunks_in<'c, I>(chunks: I, allocator: &'a Allocator) -> Result<Self, Utf8Error>
+    where
+        I: IntoIterator<Item = &'c [u8]>,
+        I::IntoIter: Clone,
+    {
+        let chunks = chunks.into_iter();
+        let len = chunks.clone().map(<[u8]>::len).sum();
+        let bytes = allocator.alloc_slice_fill_copy(len, 0u8);
+        let mut start = 0;
+        for chunk in chunks {
+            let end = start + chunk.len();
+            bytes[start..end].copy_from_slice(chunk);
+            start = end;
+        }
+        str::from_utf8(bytes).map(Self)
+    }
+
+    /// Create an `Atom` in the arena, borrowing `text` directly if it's already stored in `allocator`,
+    /// rather than copying it.
+    ///
+    /// Saves allocations in AST-to-AST transforms, which often re-create `Atom`s from strings
+    /// which are already in the arena, but with a shorter lifetime than `'a`.
+    ///
+    /// Checking whether `text` is in the arena requires iterating over the arena's chunks,
+    /// so this is more expensive than copying a short string.
+    ///
+    /// # Safety
+    /// If `text` is in `allocator`, caller must ensure its bytes are not mutated for `'a`
+    /// (e.g. it's not the buffer of an arena `String` which is later modified).
+    /// This always holds if `text` is the content of another `Atom` or an arena `&str`.
+    #[allow(unsafe_code)]
+    pub unsafe fn new_in_dedup(text: &str, allocator: &'a Allocator) -> Self {
+        if text.is_empty() {
+            return Self("");
+        }
+        if is_in_allocator(text, allocator) {
+            // SAFETY: `text` is within an allocated region of the arena, which lives for `'a`.
+            // Caller guarantees it is not mutated for `'a`.
+            Self(unsafe { &*(text as *const str) })
+        } else {
+            Self(allocator.alloc_str(text))
+        }
+    }
+
+    /// Create an `Atom` from a `Cow<str>`.
+    ///
+    /// A borrowed string is used as is, without copying. An owned `String` is copied into the arena,
+    /// as the `Atom` cannot take ownership of its heap allocation.
+    pub fn from_cow_in(cow: Cow<'a, str>, allocator: &'a Allocator) -> Self {
+        match cow {
+            Cow::Borrowed(s) => Self(s),
+            Cow::Owned(s) => Self(allocator.alloc_str(&s)),
+        }
+    }
+
+    /// Create an `Atom` in `const` context.
+    ///
+    /// # Examples
+    /// ```
+    /// const UNDEFINED: Atom<'static> = Atom::new_const("undefined");
+    /// ```
     #[inline]
-    pub fn as_str(&self) -> &str {
+    pub const fn new_const(s: &'a str) -> Self {
+        Self(s)
+    }
+
+    /// Get length of `Atom` in bytes, in `const` context.
+    ///
+    /// Same as `len` (via `Deref` to `str`), but usable in `const` assertions.
+    ///
+    /// # Examples
+    /// ```
+    /// const UNDEFINED: Atom<'static> = Atom::new_const("undefined");
+    /// const _: () = assert!(UNDEFINED.len_const() == 9);
+    /// ```
+    #[inline]
+    pub const fn len_const(&self) -> usize {
+        self.0.len()
+    }
+
+    /// Get length of `Atom` in UTF-16 code units, as used for offsets by JS engines,
+    /// source maps and LSP positions.
+    ///
+    /// Characters outside the Basic Multilingual Plane count as 2 (a surrogate pair).
+    /// Computed from the UTF-8 bytes directly, without encoding to UTF-16.
+    pub fn utf16_len(&self) -> usize {
+        // Every char contributes 1 unit for its leading byte (i.e. any byte which is not
+        // a continuation byte `0b10xx_xxxx`), plus 1 more if it's 4 bytes long (`0b1111_0xxx`).
+        // 4-byte UTF-8 sequences are exactly the chars which are surrogate pairs in UTF-16.
+        self.0
+            .bytes()
+            .map(|b| match b {
+                0x80..=0xBF => 0,
+                0xF0..=0xFF => 2,
+                _ => 1,
+            })
+            .sum()
+    }
+
+    /// Get string content as a `&str` slice.
+    ///
+    /// The slice borrows the underlying string data (arena or source text) for `'a`,
+    /// not the `Atom`, so it can be kept after the `Atom` is dropped.
+    #[inline]
+    pub fn as_str(&self) -> &'a str {
         self.0
     }
 
+    /// Get a `'static` `Atom` for a JS keyword or commonly used global identifier
+    /// (e.g. `const`, `undefined`, `React`), or `None` if `text` is not one of them.
+    ///
+    /// The returned `Atom` does not borrow `text`, so can be kept after the source text is dropped.
+    pub fn keyword(text: &str) -> Option<Atom<'static>> {
+        if let Some(keyword) = Keyword::from_text(text) {
+            return Some(Atom(keyword.as_str()));
+        }
+        // Commonly used identifiers which are not keywords
+        let s = match text {
+            "arguments" => "arguments",
+            "async" => "async",
+            "constructor" => "constructor",
+            "from" => "from",
+            "get" => "get",
+            "of" => "of",
+            "set" => "set",
+            "undefined" => "undefined",
+            "Array" => "Array",
+            "Boolean" => "Boolean",
+            "Error" => "Error",
+            "JSON" => "JSON",
+            "Map" => "Map",
+            "Math" => "Math",
+            "NaN" => "NaN",
+            "Number" => "Number",
+            "Object" => "Object",
+            "Promise" => "Promise",
+            "React" => "React",
+            "Reflect" => "Reflect",
+            "RegExp" => "RegExp",
+            "Set" => "Set",
+            "String" => "String",
+            "Symbol" => "Symbol",
+            "console" => "console",
+            "document" => "document",
+            "exports" => "exports",
+            "globalThis" => "globalThis",
+            "module" => "module",
+            "process" => "process",
+            "require" => "require",
+            "window" => "window",
+            _ => return None,
+        };
+        Some(Atom(s))
+    }
+
+    /// Get the [`Keyword`] this `Atom` is, or `None` if it's not a keyword.
+    ///
+    /// `Atom`s longer than the longest keyword are rejected without any string comparison.
+    #[inline]
+    pub fn as_keyword(&self) -> Option<Keyword> {
+        Keyword::from_text(self.as_str())
+    }
+
+    /// Create an `Atom` from an iterator of `char`s, encoding them into the arena.
+    pub fn from_chars_in<I>(chars: I, allocator: &'a Allocator) -> Self
+    where
+        I: IntoIterator<Item = char>,
+    {
+        let mut s = oxc_allocator::String::new_in(allocator);
+        s.extend(chars);
+        Self::from(s)
+    }
+
+    /// Create an `Atom` in the arena containing this `Atom`'s content repeated `n` times.
+    ///
+    /// # Panics
+    /// Panics if length of result would overflow `usize`.
+    pub fn repeat_in<'b>(&self, n: usize, allocator: &'b Allocator) -> Atom<'b> {
+        let len = self.len().checked_mul(n).expect("capacity overflow");
+        let mut s = oxc_allocator::String::with_capacity_in(len, allocator);
+        for _ in 0..n {
+            s.push_str(self.as_str());
+        }
+        Atom::from(s)
+    }
+
+    /// Create an `Atom` in the arena by joining `parts` with `sep` between each.
+    ///
+    /// Total length is calculated up front, so result is written into a single allocation.
+    /// Joining no parts produces an empty `Atom` without allocating.
+    ///
+    /// # Panics
+    /// Panics if length of result would overflow `usize`.
+    pub fn join_in(parts: &[&str], sep: &str, allocator: &'a Allocator) -> Self {
+        let Some((first, rest)) = parts.split_first() else { return Self("") };
+
+        let len = rest
+            .iter()
+            .try_fold(first.len(), |len, part| len.checked_add(sep.len())?.checked_add(part.len()))
+            .expect("capacity overflow");
+        let mut s = oxc_allocator::String::with_capacity_in(len, allocator);
+        s.push_str(first);
+        for part in rest {
+            s.push_str(sep);
+            s.push_str(part);
+        }
+        Self::from(s)
+    }
+
+    /// Get `Atom` with `prefix` removed, or `None` if it doesn't start with `prefix`.
+    ///
+    /// Unlike `str::strip_prefix`, the result is an `Atom<'a>` sharing the same string data,
+    /// rather than a `&str` borrowing `self`.
+    pub fn strip_prefix(&self, prefix: &str) -> Option<Self> {
+        self.0.strip_prefix(prefix).map(Self)
+    }
+
+    /// Get `Atom` with `suffix` removed, or `None` if it doesn't end with `suffix`.
+    ///
+    /// Unlike `str::strip_suffix`, the result is an `Atom<'a>` sharing the same string data,
+    /// rather than a `&str` borrowing `self`.
+    pub fn strip_suffix(&self, suffix: &str) -> Option<Self> {
+        self.0.strip_suffix(suffix).map(Self)
+    }
+
+    /// Get `Atom` with leading and trailing whitespace removed, sharing the same string data.
+    ///
+    /// Whitespace is as defined by JS (`WhiteSpace` and `LineTerminator`), same as
+    /// `String.prototype.trim`. This differs slightly from `str::trim`, which uses the Unicode
+    /// `White_Space` property: U+FEFF is trimmed, and U+0085 is not.
+    #[must_use]
+    pub fn trim(&self) -> Atom<'a> {
+        Self(self.0.trim_matches(is_js_whitespace))
+    }
+
+    /// Get `Atom` with leading whitespace removed, sharing the same string data.
+    ///
+    /// See [`Atom::trim`] for definition of whitespace.
+    #[must_use]
+    pub fn trim_start(&self) -> Atom<'a> {
+        Self(self.0.trim_start_matches(is_js_whitespace))
+    }
+
+    /// Get `Atom` with trailing whitespace removed, sharing the same string data.
+    ///
+    /// See [`Atom::trim`] for definition of whitespace.
+    #[must_use]
+    pub fn trim_end(&self) -> Atom<'a> {
+        Self(self.0.trim_end_matches(is_js_whitespace))
+    }
+
+    /// Get `Atom` with each run of ASCII whitespace replaced by a single space.
+    ///
+    /// Leading and trailing whitespace is collapsed too, not removed (use [`Atom::trim`] for that).
+    /// If there is nothing to collapse (only single spaces), returns `self` without allocating.
+    #[must_use]
+    pub fn collapse_whitespace_in(&self, allocator: &'a Allocator) -> Atom<'a> {
+        let bytes = self.0.as_bytes();
+        let needs_collapse = bytes.iter().enumerate().any(|(i, &b)| {
+            b.is_ascii_whitespace() && (b != b' ' || (i > 0 && bytes[i - 1].is_ascii_whitespace()))
+        });
+        if !needs_collapse {
+            return self.clone();
+        }
+
+        let mut s = oxc_allocator::String::with_capacity_in(self.0.len(), allocator);
+        let mut in_whitespace = false;
+        for c in self.0.chars() {
+            if c.is_ascii_whitespace() {
+                if !in_whitespace {
+                    s.push(' ');
+                    in_whitespace = true;
+                }
+            } else {
+                s.push(c);
+                in_whitespace = false;
+            }
+        }
+        Self::from(s)
+    }
+
+    /// Cheap pre-filter for equality. Returns `true` if `self` and `other` are definitely not equal.
+    ///
+    /// `false` means "maybe equal" - a full comparison is required to know.
+    /// `Atom` has no inline representation to inspect, so this only compares lengths,
+    /// without reading string data.
+    #[inline]
+    pub fn quick_ne(&self, other: &Atom<'_>) -> bool {
+        self.0.len() != other.0.len()
+    }
+
+    /// Compare with `other`, ignoring ASCII case.
+    ///
+    /// Strings which differ only in ASCII case are ordered case-sensitively, so this is a total order
+    /// (e.g. for deterministic sorting). Non-ASCII bytes are compared exactly.
+    pub fn cmp_ignore_ascii_case(&self, other: &Atom<'_>) -> Ordering {
+        let lhs = self.0.bytes().map(|b| b.to_ascii_lowercase());
+        let rhs = other.0.bytes().map(|b| b.to_ascii_lowercase());
+        lhs.cmp(rhs).then_with(|| self.0.cmp(other.0))
+    }
+
     #[inline]
     pub fn into_string(self) -> String {
         String::from(self.as_str())
@@ -44,6 +380,102 @@ impl<'a> Atom<'a> {
     pub fn to_compact_str(&self) -> CompactStr {
         CompactStr::new(self.as_str())
     }
+
+    /// Get string content as a `&'static str`, without copying.
+    ///
+    /// `Atom` never stores its string inline, so this just extends the lifetime of the underlying
+    /// string data. Use [`Atom::leak_into`] for a safe alternative which copies.
+    ///
+    /// # Safety
+    /// Caller must ensure the string data this `Atom` points to lives forever and is never mutated
+    /// (e.g. it's in an `Allocator` which is leaked, or in a `&'static str`).
+    #[allow(unsafe_code)]
+    #[inline]
+    pub unsafe fn leak(self) -> &'static str {
+        // SAFETY: Caller guarantees string data lives forever
+        unsafe { &*(self.as_str() as *const str) }
+    }
+
+    /// Copy string content into a `'static` allocator, and return an `Atom<'static>`.
+    ///
+    /// Useful for building long-lived global tables from `Atom`s with a shorter lifetime.
+    #[inline]
+    pub fn leak_into(self, allocator: &'static Allocator) -> Atom<'static> {
+        Atom(allocator.alloc_str(self.as_str()))
+    }
+
+    /// Returns `true` if string data of this `Atom` is stored in `allocator`.
+    ///
+    /// Returns `false` for an `Atom` which borrows from source text, a `'static` string,
+    /// or a different `Allocator`. Useful for debugging lifetime mistakes.
+    ///
+    /// Iterates over the arena's chunks, so cost is proportional to number of chunks.
+    pub fn is_in_allocator(&self, allocator: &Allocator) -> bool {
+        is_in_allocator(self.0, allocator)
+    }
+
+    /// Get string content as a `Cow::Borrowed`, for passing to APIs which take `Cow<str>`.
+    ///
+    /// Does not allocate.
+    #[inline]
+    pub fn cow(&self) -> Cow<'_, str> {
+        Cow::Borrowed(self.as_str())
+    }
+
+    /// Convert to a `Cow::Borrowed` which borrows the underlying string data for `'a`.
+    ///
+    /// `Atom` never stores its string inline, so unlike [`Atom::cow`], the `Cow` outlives the `Atom`.
+    /// Does not allocate.
+    #[inline]
+    pub fn into_cow(self) -> Cow<'a, str> {
+        Cow::Borrowed(self.as_str())
+    }
+
+    /// Append `Atom` to a `String`.
+    ///
+    /// Faster than `write!(out, "{atom}")`, as it bypasses formatting machinery.
+    #[inline]
+    pub fn write_to(&self, out: &mut String) {
+        out.push_str(self.as_str());
+    }
+
+    /// Append `Atom` to an arena `String`.
+    ///
+    /// Faster than `write!(out, "{atom}")`, as it bypasses formatting machinery.
+    #[inline]
+    pub fn write_to_in(&self, out: &mut oxc_allocator::String<'_>) {
+        out.push_str(self.as_str());
+    }
+}
+
+/// Check if all of `s` is within memory which has been allocated in `allocator`.
+#[allow(unsafe_code)]
+fn is_in_allocator(s: &str, allocator: &Allocator) -> bool {
+    let start = s.as_ptr() as usize;
+    let end = start + s.len();
+    // SAFETY: Only addresses of chunks are compared, their contents are not read,
+    // and nothing is allocated in `allocator` while iterating
+    let mut chunks = unsafe { allocator.iter_allocated_chunks_raw() };
+    chunks.any(|(chunk_ptr, chunk_len)| {
+        let chunk_start = chunk_ptr as usize;
+        chunk_start <= start && end <= chunk_start + chunk_len
+    })
+}
+
+/// Check if `c` is JS `WhiteSpace` or `LineTerminator`.
+///
+/// <https://tc39.es/ecma262/#sec-white-space>
+/// <https://tc39.es/ecma262/#sec-line-terminators>
+fn is_js_whitespace(c: char) -> bool {
+    matches!(
+        c,
+        // <TAB>, <LF>, <VT>, <FF>, <CR>, <SP>
+        '\t' | '\n' | '\u{b}' | '\u{c}' | '\r' | ' '
+        // <NBSP>, <ZWNBSP>, <LS>, <PS>
+        | '\u{a0}' | '\u{feff}' | '\u{2028}' | '\u{2029}'
+        // Other chars in Unicode "Space_Separator" (Zs) category
+        | '\u{1680}' | '\u{2000}'..='\u{200a}' | '\u{202f}' | '\u{205f}' | '\u{3000}'
+    )
 }
 
 impl<'a> From<&'a str> for Atom<'a> {
@@ -52,6 +484,12 @@ impl<'a> From<&'a str> for Atom<'a> {
     }
 }
 
+impl<'a> From<oxc_allocator::String<'a>> for Atom<'a> {
+    fn from(s: oxc_allocator::String<'a>) -> Self {
+        Self(s.into_bump_str())
+    }
+}
+
 impl<'a> Deref for Atom<'a> {
     type Target = str;
 
@@ -84,12 +522,40 @@ impl<'a> PartialEq<Atom<'a>> for &str {
     }
 }
 
+impl<'a> PartialEq<Atom<'a>> for String {
+    fn eq(&self, other: &Atom<'a>) -> bool {
+        self.as_str() == other.as_str()
+    }
+}
+
+impl<'a> PartialEq<Atom<'a>> for &String {
+    fn eq(&self, other: &Atom<'a>) -> bool {
+        self.as_str() == other.as_str()
+    }
+}
+
+impl<'a> PartialEq<Atom<'a>> for Cow<'_, str> {
+    fn eq(&self, other: &Atom<'a>) -> bool {
+        self.as_ref() == other.as_str()
+    }
+}
+
 impl<'a> PartialEq<str> for Atom<'a> {
     fn eq(&self, other: &str) -> bool {
         self.as_str() == other
     }
 }
 
+/// Compare bytes of `Atom` with a byte slice. Bytes which are not valid UTF-8 are never equal.
+///
+/// `PartialEq<&[u8]>` cannot be implemented, as it would conflict with `PartialEq<T: AsRef<str>>`.
+/// Dereference the slice instead (`atom == *bytes`).
+impl<'a> PartialEq<[u8]> for Atom<'a> {
+    fn eq(&self, other: &[u8]) -> bool {
+        self.as_bytes() == other
+    }
+}
+
 impl<'a> hash::Hash for Atom<'a> {
     fn hash<H: hash::Hasher>(&self, hasher: &mut H) {
         self.as_str().hash(hasher);
@@ -108,6 +574,62 @@ impl<'a> fmt::Display for Atom<'a> {
     }
 }
 
+/// An [`Atom`] with its hash precomputed.
+///
+/// Useful where the same string is hashed repeatedly, e.g. as a key in symbol tables.
+/// Hashing a `HashedAtom` writes only the cached hash, without reading the string again.
+///
+/// All `HashedAtom`s which are compared with each other, or stored in the same map,
+/// must be created with the same `BuildHasher`. Otherwise equal strings will not compare equal.
+#[derive(Debug, Clone)]
+pub struct HashedAtom<'a> {
+    atom: Atom<'a>,
+    hash: u64,
+}
+
+impl<'a> HashedAtom<'a> {
+    /// Create a `HashedAtom`, computing hash of `atom` with `build_hasher`.
+    pub fn new<S: BuildHasher>(atom: Atom<'a>, build_hasher: &S) -> Self {
+        let hash = build_hasher.hash_one(atom.as_str());
+        Self { atom, hash }
+    }
+
+    /// Get the cached hash.
+    #[inline]
+    pub fn hash_value(&self) -> u64 {
+        self.hash
+    }
+
+    /// Convert into the wrapped `Atom`.
+    #[inline]
+    pub fn into_atom(self) -> Atom<'a> {
+        self.atom
+    }
+}
+
+impl<'a> Deref for HashedAtom<'a> {
+    type Target = Atom<'a>;
+
+    fn deref(&self) -> &Self::Target {
+        &self.atom
+    }
+}
+
+impl<'a> PartialEq for HashedAtom<'a> {
+    fn eq(&self, other: &Self) -> bool {
+        // Compare hashes first, as cheaper than comparing strings
+        self.hash == other.hash && self.atom.as_str() == other.atom.as_str()
+    }
+}
+
+impl<'a> Eq for HashedAtom<'a> {}
+
+impl<'a> hash::Hash for HashedAtom<'a> {
+    fn hash<H: hash::Hasher>(&self, hasher: &mut H) {
+        hasher.write_u64(self.hash);
+    }
+}
+
 /// Lifetimeless version of `Atom<'_>` which owns its own string data allocation.
 ///
 /// `CompactStr` is immutable. Use `CompactStr::into_string` for a mutable `String`.
@@ -152,6 +674,37 @@ impl CompactStr {
         Self(CompactString::new_inline(s))
     }
 
+    /// Create a temp var name `_t<n>` (e.g. `_t0`, `_t123`), without heap allocation.
+    ///
+    /// Longest possible name (`_t4294967295`) is 12 bytes, so is always stored inline,
+    /// on both 64-bit and 32-bit platforms.
+    pub fn temp(n: u32) -> Self {
+        const PREFIX: &[u8] = b"_t";
+        const MAX_LEN: usize = PREFIX.len() + 10; // `u32::MAX` has 10 digits
+
+        // Write digits backwards from end of buffer
+        let mut buf = [0u8; MAX_LEN];
+        let mut start = MAX_LEN;
+        let mut n = n;
+        loop {
+            start -= 1;
+            #[allow(clippy::cast_possible_truncation)]
+            let digit = (n % 10) as u8;
+            buf[start] = b'0' + digit;
+            n /= 10;
+            if n == 0 {
+                break;
+            }
+        }
+        start -= PREFIX.len();
+        buf[start..start + PREFIX.len()].copy_from_slice(PREFIX);
+
+        // SAFETY: Buffer from `start` onwards contains only ASCII bytes
+        #[allow(unsafe_code)]
+        let s = unsafe { str::from_utf8_unchecked(&buf[start..]) };
+        Self(CompactString::new(s))
+    }
+
     /// Get string content as a `&str` slice.
     #[inline]
     pub fn as_str(&self) -> &str {
@@ -248,3 +801,712 @@ impl Serialize for CompactStr {
         serializer.serialize_str(self.as_str())
     }
 }
+
+#[cfg(test)]
+mod test {
+    use std::{
+        borrow::Cow,
+        cmp::Ordering,
+        collections::{hash_map::RandomState, HashSet},
+        hash::{BuildHasher, Hash, Hasher},
+        mem,
+    };
+
+    use oxc_allocator::Allocator;
+
+    use super::{Atom, CompactStr, HashedAtom, MAX_INLINE_LEN};
+    use crate::Keyword;
+
+    #[test]
+    fn from_chunks_in() {
+        let allocator = Allocator::default();
+
+        let atom = Atom::from_chunks_in([&b"foo"[..], b"", b"bar"], &allocator).unwrap();
+        assert_eq!(atom, "foobar");
+
+        let atom = Atom::from_chunks_in(std::iter::empty(), &allocator).unwrap();
+        assert_eq!(atom, "");
+
+        let atom = Atom::from_chunks_in("a,b,c".split(',').map(str::as_bytes), &allocator).unwrap();
+        assert_eq!(atom, "abc");
+
+        // Multi-byte char split across chunks
+        let bytes = "a\u{1F600}b".as_bytes();
+        let atom = Atom::from_chunks_in([&bytes[..2], &bytes[2..4], &bytes[4..]], &allocator);
+        assert_eq!(atom.unwrap(), "a\u{1F600}b");
+
+        // Truncated char
+        let err = Atom::from_chunks_in([&bytes[..2], &bytes[4..]], &allocator).unwrap_err();
+        assert_eq!(err.valid_up_to(), 1);
+    }
+
+    #[test]
+    fn new_const() {
+        const LONG: Atom<'static> =
+            Atom::new_const("a string which is too long to be stored inline");
+        const _: () = assert!(LONG.len_const() == 46);
+        const EMPTY: Atom<'static> = Atom::new_const("");
+        const _: () = assert!(EMPTY.len_const() == 0);
+
+        assert_eq!(LONG, "a string which is too long to be stored inline");
+        assert_eq!(LONG.len_const(), LONG.len());
+        assert_eq!(EMPTY.len_const(), 0);
+    }
+
+    #[test]
+    fn utf16_len() {
+        for s in ["", "x", "foo_bar$123"] {
+            assert_eq!(Atom::from(s).utf16_len(), s.len(), "{s:?}");
+        }
+
+        // BMP chars are 1 unit each, regardless of UTF-8 length
+        let bmp = "café_ñ_€_中文";
+        assert_eq!(Atom::from(bmp).utf16_len(), 11);
+
+        // Astral chars are 2 units each
+        let astral = "a\u{1F600}b\u{10FFFF}";
+        assert_eq!(Atom::from(astral).utf16_len(), 6);
+
+        for s in [bmp, astral] {
+            assert_eq!(Atom::from(s).utf16_len(), s.encode_utf16().count(), "{s:?}");
+        }
+    }
+
+    #[test]
+    fn from_utf8() {
+        let allocator = Allocator::default();
+
+        // Valid bytes are borrowed
+        let bytes = "föö".as_bytes();
+        let atom = Atom::from_utf8(bytes).unwrap();
+        assert_eq!(atom, "föö");
+        assert_eq!(atom.as_str().as_ptr(), bytes.as_ptr());
+        let atom = Atom::from_utf8_lossy_in(bytes, &allocator);
+        assert_eq!(atom, "föö");
+        assert_eq!(atom.as_str().as_ptr(), bytes.as_ptr());
+
+        // Invalid bytes
+        let bytes = b"f\xC3o\xFF";
+        let err = Atom::from_utf8(bytes).unwrap_err();
+        assert_eq!(err.valid_up_to(), 1);
+        let atom = Atom::from_utf8_lossy_in(bytes, &allocator);
+        assert_eq!(atom, "f\u{FFFD}o\u{FFFD}");
+        assert_ne!(atom.as_str().as_ptr(), bytes.as_ptr());
+
+        assert_eq!(Atom::from_utf8(b"").unwrap(), "");
+    }
+
+    #[test]
+    fn from_cow_in() {
+        let allocator = Allocator::default();
+
+        // Borrowed strings are not copied
+        for s in ["short", "a string which is too long to be stored inline"] {
+            let atom = Atom::from_cow_in(Cow::Borrowed(s), &allocator);
+            assert_eq!(atom, s);
+            assert_eq!(atom.as_str().as_ptr(), s.as_ptr());
+        }
+
+        // Owned strings are copied into arena
+        let s = String::from("a string which is too long to be stored inline");
+        let ptr = s.as_ptr();
+        let atom = Atom::from_cow_in(Cow::Owned(s), &allocator);
+        assert_eq!(atom, "a string which is too long to be stored inline");
+        assert_ne!(atom.as_str().as_ptr(), ptr);
+    }
+
+    #[test]
+    fn is_in_allocator() {
+        let allocator = Allocator::default();
+        let other = Allocator::default();
+        let long = "a string which is too long to be stored inline";
+
+        for s in ["x", long] {
+            let atom = Atom::from(&*allocator.alloc_str(s));
+            assert!(atom.is_in_allocator(&allocator));
+            assert!(!atom.is_in_allocator(&other));
+            // Sub-slice of arena string
+            assert!(Atom::from(&atom.as_str()[..1]).is_in_allocator(&allocator));
+
+            let source = String::from(s);
+            assert!(!Atom::from(source.as_str()).is_in_allocator(&allocator));
+            assert!(!Atom::from(s).is_in_allocator(&allocator));
+        }
+
+        // Atom in a later chunk
+        let big = "x".repeat(100_000);
+        let atom = Atom::from(&*allocator.alloc_str(&big));
+        assert!(atom.is_in_allocator(&allocator));
+        assert!(!atom.is_in_allocator(&other));
+    }
+
+    #[test]
+    #[allow(unsafe_code)]
+    fn leak() {
+        let allocator: &'static Allocator = Box::leak(Box::default());
+        let long = "a string which is too long to be stored inline";
+
+        let leaked = {
+            let temp = Allocator::default();
+            let atom = Atom::from(&*temp.alloc_str(long));
+            let copied = atom.clone().leak_into(allocator);
+            assert_ne!(copied.as_str().as_ptr(), atom.as_str().as_ptr());
+            copied
+        };
+        assert_eq!(leaked, long);
+
+        let atom = Atom::from(&*allocator.alloc_str("foo"));
+        let ptr = atom.as_str().as_ptr();
+        // SAFETY: `allocator` is leaked, so string data lives forever
+        let s: &'static str = unsafe { atom.leak() };
+        assert_eq!(s, "foo");
+        assert_eq!(s.as_ptr(), ptr);
+    }
+
+    #[test]
+    fn cow() {
+        // Takes `Cow` by value, as APIs which accept `Cow<str>` do
+        #[allow(clippy::needless_pass_by_value)]
+        fn borrowed_ptr(cow: Cow<str>) -> Option<*const u8> {
+            match cow {
+                Cow::Borrowed(s) => Some(s.as_ptr()),
+                Cow::Owned(_) => None,
+            }
+        }
+
+        let allocator = Allocator::default();
+        for s in ["short", "a string which is too long to be stored inline"] {
+            let atom = Atom::from(&*allocator.alloc_str(s));
+            let ptr = atom.as_str().as_ptr();
+            assert_eq!(atom.cow(), s);
+            assert_eq!(borrowed_ptr(atom.cow()), Some(ptr));
+
+            // `Cow` from `into_cow` outlives the `Atom`
+            let cow = atom.into_cow();
+            assert_eq!(cow, s);
+            assert_eq!(borrowed_ptr(cow), Some(ptr));
+        }
+    }
+
+    #[test]
+    fn search() {
+        // `Atom` is a sub-slice of source, to check offsets are relative to start of `Atom`
+        let source = "import x from './dir/ünïcode/dir/index.js';";
+        let atom = Atom::from(&source[15..source.len() - 2]);
+        assert_eq!(atom, "./dir/ünïcode/dir/index.js");
+
+        // Present once
+        assert!(atom.contains("index"));
+        assert_eq!(atom.find("index"), Some(20));
+        assert_eq!(atom.rfind("index"), Some(20));
+        // Present twice
+        assert_eq!(atom.find("dir"), Some(2));
+        assert_eq!(atom.rfind("dir"), Some(16));
+        // Absent
+        assert!(!atom.contains("import"));
+        assert_eq!(atom.find("import"), None);
+        assert_eq!(atom.rfind("import"), None);
+        // At start and end
+        assert_eq!(atom.find("./"), Some(0));
+        assert_eq!(atom.rfind(".js"), Some(atom.len() - 3));
+        // Multi-byte needle. Offsets are in bytes.
+        assert_eq!(atom.find("ünï"), Some(6));
+        assert_eq!(atom.find('c'), Some(11));
+        assert_eq!(&atom[atom.find('c').unwrap()..], "code/dir/index.js");
+    }
+
+    #[test]
+    fn strip_prefix_and_suffix() {
+        let allocator = Allocator::default();
+        let atom = Atom::from(allocator.alloc_str("./some/long/path/to/module.d.ts") as &str);
+
+        // Result shares string data
+        let stripped = atom.strip_prefix("./").unwrap();
+        assert_eq!(stripped, "some/long/path/to/module.d.ts");
+        assert_eq!(stripped.as_str().as_ptr(), atom.as_str()[2..].as_ptr());
+        let stripped = atom.strip_suffix(".d.ts").unwrap();
+        assert_eq!(stripped, "./some/long/path/to/module");
+        assert_eq!(stripped.as_str().as_ptr(), atom.as_str().as_ptr());
+
+        // No match
+        assert_eq!(atom.strip_prefix("../"), None);
+        assert_eq!(atom.strip_suffix(".js"), None);
+
+        // Whole string
+        assert_eq!(atom.strip_prefix(atom.as_str()).unwrap(), "");
+        assert_eq!(atom.strip_suffix(atom.as_str()).unwrap(), "");
+
+        // Result outlives the `Atom` it was created from
+        let stripped = {
+            let atom = atom.clone();
+            atom.strip_prefix("./").unwrap()
+        };
+        assert_eq!(stripped, "some/long/path/to/module.d.ts");
+    }
+
+    #[test]
+    fn repeat_in() {
+        let allocator = Allocator::default();
+        let atom = Atom::from("ab");
+        assert_eq!(atom.repeat_in(3, &allocator), "ababab");
+        assert_eq!(atom.repeat_in(1, &allocator), "ab");
+        assert_eq!(atom.repeat_in(0, &allocator), "");
+        assert_eq!(Atom::from("").repeat_in(100, &allocator), "");
+        let repeated = Atom::from("    ").repeat_in(10, &allocator);
+        assert_eq!(repeated.len(), 40);
+        assert!(repeated.chars().all(|c| c == ' '));
+        assert_eq!(Atom::from("ü").repeat_in(2, &allocator), "üü");
+    }
+
+    #[test]
+    #[should_panic(expected = "capacity overflow")]
+    fn repeat_in_overflow() {
+        let allocator = Allocator::default();
+        Atom::from("ab").repeat_in(usize::MAX / 2 + 1, &allocator);
+    }
+
+    #[test]
+    fn hashed_atom() {
+        let build_hasher = RandomState::new();
+        let source = "foo bar foo";
+        let foo1 = HashedAtom::new(Atom::from(&source[0..3]), &build_hasher);
+        let bar = HashedAtom::new(Atom::from(&source[4..7]), &build_hasher);
+        let foo2 = HashedAtom::new(Atom::from(&source[8..11]), &build_hasher);
+
+        assert_eq!(foo1, foo2);
+        assert_ne!(foo1, bar);
+        assert_eq!(foo1.hash_value(), foo2.hash_value());
+        assert_eq!(foo1.hash_value(), build_hasher.hash_one(&source[0..3]));
+        assert_eq!(build_hasher.hash_one(&foo1), build_hasher.hash_one(&foo2));
+        assert_eq!(*foo1, "foo");
+        assert_eq!(foo1.len(), 3);
+        assert_eq!(foo2.into_atom(), "foo");
+
+        let set = [foo1, bar].into_iter().collect::<HashSet<_>>();
+        assert!(set.contains(&HashedAtom::new(Atom::from("foo"), &build_hasher)));
+        assert!(!set.contains(&HashedAtom::new(Atom::from("qux"), &build_hasher)));
+    }
+
+    #[test]
+    fn hashed_atom_does_not_rehash_string() {
+        /// `Hasher` which records how many bytes are written to it
+        #[derive(Default)]
+        struct CountingHasher(usize);
+
+        impl Hasher for CountingHasher {
+            fn finish(&self) -> u64 {
+                0
+            }
+
+            fn write(&mut self, bytes: &[u8]) {
+                self.0 += bytes.len();
+            }
+        }
+
+        let long = "x".repeat(1000);
+        let atom = HashedAtom::new(Atom::from(long.as_str()), &RandomState::new());
+        let mut hasher = CountingHasher::default();
+        atom.hash(&mut hasher);
+        assert_eq!(hasher.0, 8);
+    }
+
+    #[test]
+    fn keyword() {
+        let source = String::from("const x = undefined; React.foo; implements;");
+        for text in ["const", "undefined", "React", "implements"] {
+            let start = source.find(text).unwrap();
+            let text = &source[start..start + text.len()];
+            let atom = Atom::keyword(text).unwrap();
+            assert_eq!(atom, text);
+            assert_eq!(atom, Atom::from(text));
+            // Does not borrow from `source`
+            assert_ne!(atom.as_str().as_ptr(), text.as_ptr());
+        }
+        let atom = Atom::keyword(&source[..5]).unwrap();
+        drop(source);
+        assert_eq!(atom, "const");
+
+        assert_eq!(Atom::keyword("x"), None);
+        assert_eq!(Atom::keyword("foo"), None);
+        assert_eq!(Atom::keyword(""), None);
+        // Case sensitive
+        assert_eq!(Atom::keyword("Const"), None);
+        assert_eq!(Atom::keyword("react"), None);
+    }
+
+    #[test]
+    #[allow(unsafe_code)]
+    fn new_in_dedup() {
+        let allocator = Allocator::default();
+
+        // String already in arena is borrowed
+        let text = allocator.alloc_str("hello world");
+        // SAFETY: `text` is not mutated
+        let atom = unsafe { Atom::new_in_dedup(text, &allocator) };
+        assert_eq!(atom, "hello world");
+        assert_eq!(atom.as_ptr(), text.as_ptr());
+        // Including a substring of it
+        // SAFETY: `text` is not mutated
+        let atom = unsafe { Atom::new_in_dedup(&text[6..], &allocator) };
+        assert_eq!(atom, "world");
+        assert_eq!(atom.as_ptr(), text[6..].as_ptr());
+
+        // String in a previous chunk of arena
+        let first = allocator.alloc_str("first chunk");
+        allocator.alloc_slice_fill_copy(64 * 1024, 0u8);
+        // SAFETY: `first` is not mutated
+        let atom = unsafe { Atom::new_in_dedup(first, &allocator) };
+        assert_eq!(atom.as_ptr(), first.as_ptr());
+
+        // Foreign string is copied
+        let foreign = String::from("hello world");
+        // SAFETY: `foreign` is not in arena
+        let atom = unsafe { Atom::new_in_dedup(&foreign, &allocator) };
+        assert_eq!(atom, "hello world");
+        assert_ne!(atom.as_ptr(), foreign.as_ptr());
+
+        // String in a different arena is copied
+        let other_allocator = Allocator::default();
+        let other = other_allocator.alloc_str("other");
+        // SAFETY: `other` is not in arena
+        let atom = unsafe { Atom::new_in_dedup(other, &allocator) };
+        assert_eq!(atom, "other");
+        assert_ne!(atom.as_ptr(), other.as_ptr());
+
+        // SAFETY: Empty string
+        assert_eq!(unsafe { Atom::new_in_dedup("", &allocator) }, "");
+    }
+
+    #[test]
+    fn is_ascii() {
+        assert!(Atom::from("foo").is_ascii());
+        assert!(Atom::from("a_very_long_identifier_name_$123").is_ascii());
+        assert!(!Atom::from("a_very_long_identifier_namé").is_ascii());
+        assert!(!Atom::from("\u{1F600}").is_ascii());
+        assert!(Atom::from("").is_ascii());
+    }
+
+    #[test]
+    fn as_keyword() {
+        assert_eq!(Atom::from("function").as_keyword(), Some(Keyword::Function));
+        assert_eq!(Atom::from("await").as_keyword(), Some(Keyword::Await));
+        assert_eq!(Atom::from("let").as_keyword(), Some(Keyword::Let));
+        assert_eq!(Atom::from("foo").as_keyword(), None);
+        assert_eq!(Atom::from("").as_keyword(), None);
+        // Longer than any keyword
+        assert_eq!(Atom::from("functionWithLongName").as_keyword(), None);
+    }
+
+    #[test]
+    fn from_chars_in() {
+        let allocator = Allocator::default();
+        let atom = Atom::from_chars_in(['h', 'é', 'l', 'l', 'o'], &allocator);
+        assert_eq!(atom, "héllo");
+        assert_eq!(atom.len(), 6);
+
+        let atom = Atom::from_chars_in("a\u{1F600}b".chars().rev(), &allocator);
+        assert_eq!(atom, "b\u{1F600}a");
+
+        let atom = Atom::from_chars_in(std::iter::empty(), &allocator);
+        assert_eq!(atom, "");
+    }
+
+    #[test]
+    fn join_in() {
+        let allocator = Allocator::default();
+        assert_eq!(Atom::join_in(&["a", "b", "c"], ".", &allocator), "a.b.c");
+        assert_eq!(Atom::join_in(&["", "foo", ""], "/", &allocator), "/foo/");
+        assert_eq!(Atom::join_in(&["a", "b"], "", &allocator), "ab");
+
+        let parts = ["node_modules", "@scope", "package-name", "dist", "index.mjs"];
+        let atom = Atom::join_in(&parts, "/", &allocator);
+        assert_eq!(atom, "node_modules/@scope/package-name/dist/index.mjs");
+        assert!(atom.len() > MAX_INLINE_LEN);
+
+        // Single part has no separator
+        assert_eq!(Atom::join_in(&["foo"], ".", &allocator), "foo");
+        // No parts
+        assert_eq!(Atom::join_in(&[], ".", &allocator), "");
+    }
+
+    #[test]
+    fn eq_atom() {
+        let atom = Atom::from("foo");
+
+        let s = String::from("foo");
+        let s_ref = &s;
+        assert!(s == atom);
+        assert!(s_ref == atom);
+        assert!(atom == s);
+        let s = String::from("bar");
+        let s_ref = &s;
+        assert!(s != atom);
+        assert!(s_ref != atom);
+        assert!(atom != s);
+
+        assert!(Cow::Borrowed("foo") == atom);
+        assert!(Cow::<str>::Owned(String::from("foo")) == atom);
+        assert!(atom == Cow::Borrowed("foo"));
+        assert!(Cow::Borrowed("bar") != atom);
+        assert!(Cow::<str>::Owned(String::from("bar")) != atom);
+        assert!(atom != Cow::Borrowed("bar"));
+    }
+
+    #[test]
+    fn eq_bytes() {
+        let atom = Atom::from("café");
+        let bytes: &[u8] = b"caf\xC3\xA9";
+        assert!(atom == *bytes);
+        assert!(atom == b"caf\xC3\xA9"[..]);
+        assert!(atom != b"cafe"[..]);
+        assert!(atom != b"caf"[..]);
+        assert!(atom != b""[..]);
+        assert!(Atom::from("") == b""[..]);
+
+        // Invalid UTF-8 is not equal
+        assert!(atom != b"caf\xC3"[..]);
+        assert!(atom != b"caf\xFF\xA9"[..]);
+        assert!(Atom::from("\u{FFFD}") != b"\xFF"[..]);
+    }
+
+    #[test]
+    fn as_str_outlives_atom() {
+        let allocator = Allocator::default();
+        // `atom` goes out of scope at end of block, but `&str` remains valid
+        let s = {
+            let atom = Atom::from_chars_in("foo".chars(), &allocator);
+            atom.as_str()
+        };
+        assert_eq!(s, "foo");
+    }
+
+    #[test]
+    fn quick_ne() {
+        let long = "a string which is too long to be stored inline";
+        let atoms = ["", "a", "b", "ab", long, &long.to_uppercase()];
+        for a in atoms.map(Atom::from) {
+            for b in atoms.map(Atom::from) {
+                // If `quick_ne` says not equal, they must not be equal
+                if a.quick_ne(&b) {
+                    assert_ne!(a, b);
+                }
+                assert_eq!(a.quick_ne(&b), a.len() != b.len());
+            }
+        }
+
+        // Equal strings from different sources are never rejected
+        let allocator = Allocator::default();
+        let copy = Atom::from(&*allocator.alloc_str(long));
+        assert!(!Atom::from(long).quick_ne(&copy));
+        // Same length but not equal is "maybe equal"
+        assert!(!Atom::from("a").quick_ne(&Atom::from("b")));
+    }
+
+    #[test]
+    fn cmp_ignore_ascii_case() {
+        let mut atoms =
+            ["Cherry", "banana", "apple", "Banana", "cherry", "Apple", "b"].map(Atom::from);
+        atoms.sort_by(Atom::cmp_ignore_ascii_case);
+        assert_eq!(atoms, ["Apple", "apple", "b", "Banana", "banana", "Cherry", "cherry"]);
+
+        // Order does not depend on input order
+        let mut atoms2 = atoms.clone();
+        atoms2.reverse();
+        atoms2.sort_by(Atom::cmp_ignore_ascii_case);
+        assert_eq!(atoms2, atoms);
+
+        let mut atoms = ["Banana", "apple", "Cherry"].map(Atom::from);
+        atoms.sort_by(Atom::cmp_ignore_ascii_case);
+        assert_eq!(atoms, ["apple", "Banana", "Cherry"]);
+
+        // Equal only if identical
+        let foo = Atom::from("foo");
+        assert_eq!(foo.cmp_ignore_ascii_case(&Atom::from("foo")), Ordering::Equal);
+        assert_eq!(foo.cmp_ignore_ascii_case(&Atom::from("FOO")), Ordering::Greater);
+        assert_eq!(foo.cmp_ignore_ascii_case(&Atom::from("foo_")), Ordering::Less);
+
+        // Non-ASCII is not case-folded
+        assert_eq!(Atom::from("É").cmp_ignore_ascii_case(&Atom::from("e")), Ordering::Greater);
+        assert_eq!(Atom::from("é").cmp_ignore_ascii_case(&Atom::from("É")), Ordering::Greater);
+    }
+
+    #[test]
+    fn trim() {
+        let allocator = Allocator::default();
+        let atom = Atom::from(allocator.alloc_str(" \t\n  some JSX text\r\n  ") as &str);
+
+        let trimmed = atom.trim();
+        assert_eq!(trimmed, "some JSX text");
+        assert_eq!(trimmed.as_str().as_ptr(), atom.as_str()[5..].as_ptr());
+        let trimmed = atom.trim_start();
+        assert_eq!(trimmed, "some JSX text\r\n  ");
+        assert_eq!(trimmed.as_str().as_ptr(), atom.as_str()[5..].as_ptr());
+        let trimmed = atom.trim_end();
+        assert_eq!(trimmed, " \t\n  some JSX text");
+        assert_eq!(trimmed.as_str().as_ptr(), atom.as_str().as_ptr());
+
+        // All whitespace
+        let atom = Atom::from(" \n\t ");
+        assert_eq!(atom.trim(), "");
+        assert_eq!(atom.trim_start(), "");
+        assert_eq!(atom.trim_end(), "");
+
+        // No whitespace
+        let atom = Atom::from("foo");
+        assert_eq!(atom.trim(), "foo");
+        assert_eq!(atom.trim().as_str().as_ptr(), atom.as_str().as_ptr());
+        assert_eq!(atom.trim_start(), "foo");
+        assert_eq!(atom.trim_end(), "foo");
+
+        // Unicode whitespace, as defined by JS
+        let atom = Atom::from("\u{feff}\u{a0}\u{2028}\u{3000}foo\u{2029}\u{202f}");
+        assert_eq!(atom.trim(), "foo");
+        // U+0085 (NEL) is whitespace in Unicode, but not in JS
+        let atom = Atom::from("\u{85}foo\u{85}");
+        assert_eq!(atom.trim(), "\u{85}foo\u{85}");
+    }
+
+    #[test]
+    fn collapse_whitespace_in() {
+        let allocator = Allocator::default();
+
+        for (input, expected) in [
+            ("a  b", "a b"),
+            ("a\tb", "a b"),
+            ("a \t\r\n b\n\nc", "a b c"),
+            ("  \tcafé  \u{1F600}\n", " café \u{1F600} "),
+            ("\t", " "),
+            (" \n ", " "),
+        ] {
+            assert_eq!(Atom::from(input).collapse_whitespace_in(&allocator), expected, "{input:?}");
+        }
+
+        // Nothing to collapse - same string data is returned
+        for input in ["", "foo", "a b c", " a ", "\u{a0}\u{a0}x\u{2028}\u{2028}"] {
+            let atom = Atom::from(input);
+            let collapsed = atom.collapse_whitespace_in(&allocator);
+            assert_eq!(collapsed, input);
+            assert_eq!(collapsed.as_str().as_ptr(), atom.as_str().as_ptr(), "{input:?}");
+        }
+    }
+
+    /// Strings which contain multi-byte UTF-8 chars at, or either side of, the inline boundary
+    fn boundary_strings() -> Vec<String> {
+        let mut strings = vec![];
+        for len in MAX_INLINE_LEN - 4..=MAX_INLINE_LEN + 4 {
+            for c in ['\u{e9}', '\u{2028}', '\u{1d4cd}'] {
+                let prefix_len = len - c.len_utf8();
+                strings.push(format!("{}{c}", "x".repeat(prefix_len)));
+                strings.push(format!("{c}{}", "x".repeat(prefix_len)));
+            }
+        }
+        strings
+    }
+
+    #[test]
+    fn compact_str_non_ascii() {
+        for s in boundary_strings() {
+            let compact = CompactStr::new(&s);
+            assert_eq!(compact.as_str(), s);
+            assert_eq!(compact.len(), s.len());
+            assert_eq!(compact.0.is_heap_allocated(), s.len() > mem::size_of::<String>());
+            assert_eq!(CompactStr::from(s.as_str()), compact);
+            assert_eq!(CompactStr::from(s.clone()).as_str(), s);
+            assert_eq!(compact.clone().into_string(), s);
+        }
+    }
+
+    #[test]
+    fn new_const_non_ascii() {
+        for s in boundary_strings().into_iter().filter(|s| s.len() <= MAX_INLINE_LEN) {
+            let s: &'static str = Box::leak(s.into_boxed_str());
+            let compact = CompactStr::new_const(s);
+            assert_eq!(compact.as_str(), s);
+            assert_eq!(compact.len(), s.len());
+            assert!(!compact.0.is_heap_allocated());
+        }
+    }
+
+    #[test]
+    fn compact_str_temp() {
+        for (n, expected) in [
+            (0, "_t0"),
+            (7, "_t7"),
+            (10, "_t10"),
+            (9999, "_t9999"),
+            (123_456, "_t123456"),
+            (u32::MAX, "_t4294967295"),
+        ] {
+            let temp = CompactStr::temp(n);
+            assert_eq!(temp.as_str(), expected);
+            assert!(temp.len() <= MAX_INLINE_LEN);
+            assert!(!temp.0.is_heap_allocated(), "{n}");
+        }
+
+        for n in (0..100_000).chain(u32::MAX - 100..=u32::MAX) {
+            assert_eq!(CompactStr::temp(n).as_str(), format!("_t{n}"));
+        }
+    }
+
+    #[cfg(target_pointer_width = "64")]
+    #[test]
+    fn new_const_max_len_64() {
+        // 14 bytes + 2-byte char
+        const S1: CompactStr = CompactStr::new_const("xxxxxxxxxxxxxx\u{e9}");
+        // 13 bytes + 3-byte char
+        const S2: CompactStr = CompactStr::new_const("xxxxxxxxxxxxx\u{2028}");
+        // 12 bytes + 4-byte char
+        const S3: CompactStr = CompactStr::new_const("xxxxxxxxxxxx\u{1d4cd}");
+        assert_eq!(MAX_INLINE_LEN, 16);
+        assert_eq!(S1.as_str(), "xxxxxxxxxxxxxx\u{e9}");
+        assert_eq!(S2.as_str(), "xxxxxxxxxxxxx\u{2028}");
+        assert_eq!(S3.as_str(), "xxxxxxxxxxxx\u{1d4cd}");
+        for s in [S1, S2, S3] {
+            assert_eq!(s.len(), 16);
+        }
+    }
+
+    #[cfg(target_pointer_width = "32")]
+    #[test]
+    fn new_const_max_len_32() {
+        // 10 bytes + 2-byte char
+        const S1: CompactStr = CompactStr::new_const("xxxxxxxxxx\u{e9}");
+        // 9 bytes + 3-byte char
+        const S2: CompactStr = CompactStr::new_const("xxxxxxxxx\u{2028}");
+        // 8 bytes + 4-byte char
+        const S3: CompactStr = CompactStr::new_const("xxxxxxxx\u{1d4cd}");
+        assert_eq!(MAX_INLINE_LEN, 12);
+        assert_eq!(S1.as_str(), "xxxxxxxxxx\u{e9}");
+        assert_eq!(S2.as_str(), "xxxxxxxxx\u{2028}");
+        assert_eq!(S3.as_str(), "xxxxxxxx\u{1d4cd}");
+        for s in [S1, S2, S3] {
+            assert_eq!(s.len(), 12);
+        }
+    }
+
+    #[test]
+    #[should_panic(expected = "s.len() <= MAX_INLINE_LEN")]
+    fn new_const_too_long_non_ascii() {
+        // 1 byte too long, due to final 2-byte char
+        let s = format!("{}\u{e9}", "x".repeat(MAX_INLINE_LEN - 1));
+        let _ = CompactStr::new_const(Box::leak(s.into_boxed_str()));
+    }
+
+    #[test]
+    fn write_to() {
+        let atoms = [Atom::from("foo"), Atom::from(""), Atom::from(".bar"), Atom::from("𝓍")];
+
+        let mut out = String::from(">");
+        for atom in &atoms {
+            atom.write_to(&mut out);
+        }
+        assert_eq!(out, ">foo.bar𝓍");
+
+        let allocator = Allocator::default();
+        let mut out = oxc_allocator::String::from_str_in(">", &allocator);
+        for atom in &atoms {
+            atom.write_to_in(&mut out);
+        }
+        assert_eq!(out.as_str(), ">foo.bar𝓍");
+    }
+}
@@ -0,0 +1,112 @@
+use oxc_allocator::{Allocator, String};
+
+use crate::Atom;
+
+/// Builder for an [`Atom`], for constructing its content incrementally in the arena.
+///
+/// Wraps an arena [`String`]. [`AtomBuilder::finish`] converts to an `Atom` without copying,
+/// reusing the `String`'s buffer.
+///
+/// # Examples
+/// ```
+/// use oxc_allocator::Allocator;
+/// use oxc_span::Atom;
+///
+/// let allocator = Allocator::default();
+/// let mut builder = Atom::with_capacity_in(6, &allocator);
+/// builder.push_str("foo");
+/// builder.push('_');
+/// builder.push_str("ba");
+/// assert_eq!(builder.finish(), "foo_ba");
+/// ```
+#[derive(Debug)]
+pub struct AtomBuilder<'a>(String<'a>);
+
+impl<'a> AtomBuilder<'a> {
+    pub fn new_in(allocator: &'a Allocator) -> Self {
+        Self(String::new_in(allocator))
+    }
+
+    pub fn with_capacity_in(capacity: usize, allocator: &'a Allocator) -> Self {
+        Self(String::with_capacity_in(capacity, allocator))
+    }
+
+    #[inline]
+    pub fn push(&mut self, c: char) {
+        self.0.push(c);
+    }
+
+    #[inline]
+    pub fn push_str(&mut self, s: &str) {
+        self.0.push_str(s);
+    }
+
+    /// Get content built so far.
+    #[inline]
+    pub fn as_str(&self) -> &str {
+        self.0.as_str()
+    }
+
+    #[inline]
+    pub fn len(&self) -> usize {
+        self.0.len()
+    }
+
+    #[inline]
+    pub fn is_empty(&self) -> bool {
+        self.0.is_empty()
+    }
+
+    /// Convert to an [`Atom`].
+    ///
+    /// Content is not copied - the `Atom` points to the buffer which it was built in.
+    #[inline]
+    pub fn finish(self) -> Atom<'a> {
+        Atom::from(self.0)
+    }
+}
+
+impl<'a> Atom<'a> {
+    /// Create an [`AtomBuilder`] with space for `capacity` bytes reserved in the arena.
+    pub fn with_capacity_in(capacity: usize, allocator: &'a Allocator) -> AtomBuilder<'a> {
+        AtomBuilder::with_capacity_in(capacity, allocator)
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use oxc_allocator::Allocator;
+
+    use super::AtomBuilder;
+    use crate::Atom;
+
+    #[test]
+    fn build_identifier() {
+        let allocator = Allocator::default();
+
+        // Identifier with escapes decoded, built piece by piece as lexer would
+        let mut builder = Atom::with_capacity_in(8, &allocator);
+        assert!(builder.is_empty());
+        builder.push_str("abc");
+        builder.push('\u{0064}');
+        builder.push_str("_long_identifier_name_which_outgrows_capacity");
+        builder.push('\u{1F600}');
+        assert_eq!(builder.len(), 53);
+        let atom = builder.finish();
+        assert_eq!(atom, "abcd_long_identifier_name_which_outgrows_capacity\u{1F600}");
+        assert!(atom.is_in_allocator(&allocator));
+
+        // Short result
+        let mut builder = AtomBuilder::new_in(&allocator);
+        builder.push('x');
+        builder.push_str("y");
+        assert_eq!(builder.as_str(), "xy");
+        let atom = builder.finish();
+        assert_eq!(atom, Atom::from("xy"));
+        assert_eq!(atom.len(), 2);
+
+        // Empty result
+        let atom = AtomBuilder::new_in(&allocator).finish();
+        assert_eq!(atom, "");
+    }
+}
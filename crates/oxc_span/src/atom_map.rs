@@ -0,0 +1,195 @@
+use std::{
+    collections::hash_map::{Entry, Iter},
+    hash::BuildHasherDefault,
+};
+
+use rustc_hash::FxHashMap;
+
+use crate::Atom;
+
+/// A hash map keyed by [`Atom`], which can be queried with a plain `&str`.
+///
+/// Thin wrapper around `FxHashMap<Atom<'a>, V>`.
+/// Lookup methods take `&str`, so no `Atom` needs to be created just to query the map.
+#[derive(Debug, Clone)]
+pub struct AtomMap<'a, V>(FxHashMap<Atom<'a>, V>);
+
+impl<'a, V> Default for AtomMap<'a, V> {
+    fn default() -> Self {
+        Self(FxHashMap::default())
+    }
+}
+
+impl<'a, V> AtomMap<'a, V> {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    pub fn with_capacity(capacity: usize) -> Self {
+        Self(FxHashMap::with_capacity_and_hasher(capacity, BuildHasherDefault::default()))
+    }
+
+    pub fn len(&self) -> usize {
+        self.0.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.0.is_empty()
+    }
+
+    pub fn get(&self, key: &str) -> Option<&V> {
+        self.0.get(key)
+    }
+
+    pub fn get_mut(&mut self, key: &str) -> Option<&mut V> {
+        self.0.get_mut(key)
+    }
+
+    /// Get the `Atom` stored as key for `key`, along with its value.
+    pub fn get_key_value(&self, key: &str) -> Option<(&Atom<'a>, &V)> {
+        self.0.get_key_value(key)
+    }
+
+    pub fn contains_key(&self, key: &str) -> bool {
+        self.0.contains_key(key)
+    }
+
+    /// Insert `value` for `key`, returning the previous value if there was one.
+    pub fn insert(&mut self, key: Atom<'a>, value: V) -> Option<V> {
+        self.0.insert(key, value)
+    }
+
+    pub fn remove(&mut self, key: &str) -> Option<V> {
+        self.0.remove(key)
+    }
+
+    pub fn entry(&mut self, key: Atom<'a>) -> Entry<'_, Atom<'a>, V> {
+        self.0.entry(key)
+    }
+
+    /// Get mutable reference to value for `key`, inserting value returned by `f` if not present.
+    ///
+    /// Unlike [`AtomMap::entry`], lookup is by `&str`, and `into_atom` is only called
+    /// to create the key if `key` is not already in the map.
+    pub fn get_or_insert_with<F, I>(&mut self, key: &str, into_atom: I, f: F) -> &mut V
+    where
+        I: FnOnce(&str) -> Atom<'a>,
+        F: FnOnce() -> V,
+    {
+        let atom = match self.0.get_key_value(key) {
+            Some((atom, _)) => atom.clone(),
+            None => into_atom(key),
+        };
+        self.0.entry(atom).or_insert_with(f)
+    }
+
+    pub fn iter(&self) -> Iter<'_, Atom<'a>, V> {
+        self.0.iter()
+    }
+
+    pub fn keys(&self) -> impl Iterator<Item = &Atom<'a>> {
+        self.0.keys()
+    }
+
+    pub fn values(&self) -> impl Iterator<Item = &V> {
+        self.0.values()
+    }
+
+    pub fn clear(&mut self) {
+        self.0.clear();
+    }
+
+    /// Get the underlying `FxHashMap`.
+    pub fn into_inner(self) -> FxHashMap<Atom<'a>, V> {
+        self.0
+    }
+}
+
+impl<'a, V> FromIterator<(Atom<'a>, V)> for AtomMap<'a, V> {
+    fn from_iter<T: IntoIterator<Item = (Atom<'a>, V)>>(iter: T) -> Self {
+        Self(iter.into_iter().collect())
+    }
+}
+
+impl<'a, V> IntoIterator for AtomMap<'a, V> {
+    type Item = (Atom<'a>, V);
+    type IntoIter = std::collections::hash_map::IntoIter<Atom<'a>, V>;
+
+    fn into_iter(self) -> Self::IntoIter {
+        self.0.into_iter()
+    }
+}
+
+impl<'m, 'a, V> IntoIterator for &'m AtomMap<'a, V> {
+    type Item = (&'m Atom<'a>, &'m V);
+    type IntoIter = Iter<'m, Atom<'a>, V>;
+
+    fn into_iter(self) -> Self::IntoIter {
+        self.0.iter()
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use oxc_allocator::Allocator;
+
+    use super::AtomMap;
+    use crate::Atom;
+
+    #[test]
+    fn lookup_by_str() {
+        let allocator = Allocator::default();
+        let long = "a_very_long_identifier_which_is_not_stored_inline";
+        let long_atom = Atom::from(&*allocator.alloc_str(long));
+
+        let mut map = AtomMap::new();
+        assert!(map.is_empty());
+        assert_eq!(map.insert(Atom::from("foo"), 1), None);
+        assert_eq!(map.insert(long_atom, 2), None);
+        assert_eq!(map.insert(Atom::from("foo"), 3), Some(1));
+        assert_eq!(map.len(), 2);
+
+        assert_eq!(map.get("foo"), Some(&3));
+        assert_eq!(map.get(long), Some(&2));
+        assert_eq!(map.get(String::from(long).as_str()), Some(&2));
+        assert_eq!(map.get("bar"), None);
+        assert!(map.contains_key(long));
+        assert!(!map.contains_key(&long[1..]));
+
+        let (key, value) = map.get_key_value(long).unwrap();
+        assert_eq!(key.as_str(), long);
+        assert_eq!(*value, 2);
+
+        *map.get_mut("foo").unwrap() += 1;
+        assert_eq!(map.get("foo"), Some(&4));
+
+        assert_eq!(map.remove("foo"), Some(4));
+        assert_eq!(map.get("foo"), None);
+        assert_eq!(map.len(), 1);
+    }
+
+    #[test]
+    fn entry() {
+        let mut map = AtomMap::new();
+        *map.entry(Atom::from("x")).or_insert(0) += 1;
+        *map.entry(Atom::from("x")).or_insert(0) += 1;
+        assert_eq!(map.get("x"), Some(&2));
+
+        let allocator = Allocator::default();
+        let mut map = AtomMap::new();
+        let mut calls = 0;
+        for _ in 0..2 {
+            let value = map.get_or_insert_with(
+                "a_long_key_created_on_first_lookup_only",
+                |s| {
+                    calls += 1;
+                    Atom::from(&*allocator.alloc_str(s))
+                },
+                Vec::new,
+            );
+            value.push(1);
+        }
+        assert_eq!(calls, 1);
+        assert_eq!(map.get("a_long_key_created_on_first_lookup_only"), Some(&vec![1, 1]));
+    }
+}
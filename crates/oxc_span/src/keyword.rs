@@ -0,0 +1,285 @@
+use std::fmt;
+
+/// Reserved words of JavaScript.
+///
+/// Includes all of [`ReservedWord`](https://tc39.es/ecma262/#prod-ReservedWord),
+/// plus words which are only reserved in strict mode (`let`, `static`, `implements` etc).
+/// Contextual keywords which are never reserved (e.g. `async`, `of`) are not included.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum Keyword {
+    Await,
+    Break,
+    Case,
+    Catch,
+    Class,
+    Const,
+    Continue,
+    Debugger,
+    Default,
+    Delete,
+    Do,
+    Else,
+    Enum,
+    Export,
+    Extends,
+    False,
+    Finally,
+    For,
+    Function,
+    If,
+    Import,
+    In,
+    Instanceof,
+    New,
+    Null,
+    Return,
+    Super,
+    Switch,
+    This,
+    Throw,
+    True,
+    Try,
+    Typeof,
+    Var,
+    Void,
+    While,
+    With,
+    Yield,
+    // Reserved in strict mode only
+    Implements,
+    Interface,
+    Let,
+    Package,
+    Private,
+    Protected,
+    Public,
+    Static,
+}
+
+#[allow(clippy::enum_glob_use)]
+use Keyword::*;
+
+impl Keyword {
+    /// Length of longest keyword (`implements` and `instanceof`).
+    pub const MAX_LEN: usize = 10;
+
+    /// Get `Keyword` for `text`, or `None` if `text` is not a keyword.
+    ///
+    /// Matches on length first, so only keywords of same length as `text` are compared against,
+    /// and `text` longer than [`Keyword::MAX_LEN`] is rejected immediately.
+    pub fn from_text(text: &str) -> Option<Self> {
+        let keyword = match text.len() {
+            2 => match text {
+                "do" => Do,
+                "if" => If,
+                "in" => In,
+                _ => return None,
+            },
+            3 => match text {
+                "for" => For,
+                "let" => Let,
+                "new" => New,
+                "try" => Try,
+                "var" => Var,
+                _ => return None,
+            },
+            4 => match text {
+                "case" => Case,
+                "else" => Else,
+                "enum" => Enum,
+                "null" => Null,
+                "this" => This,
+                "true" => True,
+                "void" => Void,
+                "with" => With,
+                _ => return None,
+            },
+            5 => match text {
+                "await" => Await,
+                "break" => Break,
+                "catch" => Catch,
+                "class" => Class,
+                "const" => Const,
+                "false" => False,
+                "super" => Super,
+                "throw" => Throw,
+                "while" => While,
+                "yield" => Yield,
+                _ => return None,
+            },
+            6 => match text {
+                "delete" => Delete,
+                "export" => Export,
+                "import" => Import,
+                "public" => Public,
+                "return" => Return,
+                "static" => Static,
+                "switch" => Switch,
+                "typeof" => Typeof,
+                _ => return None,
+            },
+            7 => match text {
+                "default" => Default,
+                "extends" => Extends,
+                "finally" => Finally,
+                "package" => Package,
+                "private" => Private,
+                _ => return None,
+            },
+            8 => match text {
+                "continue" => Continue,
+                "debugger" => Debugger,
+                "function" => Function,
+                _ => return None,
+            },
+            9 => match text {
+                "interface" => Interface,
+                "protected" => Protected,
+                _ => return None,
+            },
+            10 => match text {
+                "implements" => Implements,
+                "instanceof" => Instanceof,
+                _ => return None,
+            },
+            _ => return None,
+        };
+        Some(keyword)
+    }
+
+    pub fn as_str(self) -> &'static str {
+        match self {
+            Await => "await",
+            Break => "break",
+            Case => "case",
+            Catch => "catch",
+            Class => "class",
+            Const => "const",
+            Continue => "continue",
+            Debugger => "debugger",
+            Default => "default",
+            Delete => "delete",
+            Do => "do",
+            Else => "else",
+            Enum => "enum",
+            Export => "export",
+            Extends => "extends",
+            False => "false",
+            Finally => "finally",
+            For => "for",
+            Function => "function",
+            If => "if",
+            Import => "import",
+            In => "in",
+            Instanceof => "instanceof",
+            New => "new",
+            Null => "null",
+            Return => "return",
+            Super => "super",
+            Switch => "switch",
+            This => "this",
+            Throw => "throw",
+            True => "true",
+            Try => "try",
+            Typeof => "typeof",
+            Var => "var",
+            Void => "void",
+            While => "while",
+            With => "with",
+            Yield => "yield",
+            Implements => "implements",
+            Interface => "interface",
+            Let => "let",
+            Package => "package",
+            Private => "private",
+            Protected => "protected",
+            Public => "public",
+            Static => "static",
+        }
+    }
+
+    /// Returns `true` if keyword is only reserved in strict mode code.
+    pub fn is_strict_mode_only(self) -> bool {
+        matches!(
+            self,
+            Implements | Interface | Let | Package | Private | Protected | Public | Static
+        )
+    }
+}
+
+impl fmt::Display for Keyword {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(self.as_str())
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::Keyword;
+
+    #[test]
+    fn from_text() {
+        let keywords = [
+            "await",
+            "break",
+            "case",
+            "catch",
+            "class",
+            "const",
+            "continue",
+            "debugger",
+            "default",
+            "delete",
+            "do",
+            "else",
+            "enum",
+            "export",
+            "extends",
+            "false",
+            "finally",
+            "for",
+            "function",
+            "if",
+            "import",
+            "in",
+            "instanceof",
+            "new",
+            "null",
+            "return",
+            "super",
+            "switch",
+            "this",
+            "throw",
+            "true",
+            "try",
+            "typeof",
+            "var",
+            "void",
+            "while",
+            "with",
+            "yield",
+            "implements",
+            "interface",
+            "let",
+            "package",
+            "private",
+            "protected",
+            "public",
+            "static",
+        ];
+        for text in keywords {
+            let keyword = Keyword::from_text(text).unwrap();
+            assert_eq!(keyword.as_str(), text);
+            assert_eq!(keyword.to_string(), text);
+            assert!(text.len() <= Keyword::MAX_LEN);
+        }
+        assert_eq!(keywords.iter().map(|text| text.len()).max(), Some(Keyword::MAX_LEN));
+
+        for text in ["", "x", "async", "of", "Function", "functio", "functions", "instanceOf"] {
+            assert_eq!(Keyword::from_text(text), None, "{text}");
+        }
+
+        assert!(Keyword::Let.is_strict_mode_only());
+        assert!(!Keyword::Yield.is_strict_mode_only());
+    }
+}
@@ -3,11 +3,19 @@
 //! <https://doc.rust-lang.org/beta/nightly-rustc/rustc_span>
 
 mod atom;
+mod atom_builder;
+mod atom_map;
+mod keyword;
+mod line_index;
 mod source_type;
 mod span;
 
 pub use crate::{
-    atom::{Atom, CompactStr, MAX_INLINE_LEN as ATOM_MAX_INLINE_LEN},
+    atom::{Atom, CompactStr, HashedAtom, MAX_INLINE_LEN as ATOM_MAX_INLINE_LEN},
+    atom_builder::AtomBuilder,
+    atom_map::AtomMap,
+    keyword::Keyword,
+    line_index::LineIndex,
     source_type::{Language, LanguageVariant, ModuleKind, SourceType, VALID_EXTENSIONS},
     span::{GetSpan, Span, SPAN},
 };
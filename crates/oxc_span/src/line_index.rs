@@ -0,0 +1,146 @@
+/// Index of line start offsets in a source text, for converting byte offsets to line / column.
+///
+/// Built once from the source text, and can then be queried any number of times.
+/// Recognises all ECMAScript line terminators: `\n`, `\r\n`, lone `\r`, U+2028 and U+2029.
+///
+/// Columns are measured in UTF-16 code units, as expected by editors and the Language Server Protocol.
+///
+/// # Examples
+/// ```
+/// let index = LineIndex::new("let x;\nlet y;");
+/// assert_eq!(index.line_col(11), (2, 4));
+/// ```
+#[derive(Debug, Clone)]
+pub struct LineIndex<'a> {
+    source_text: &'a str,
+    /// Byte offsets of start of each line. Always contains at least one entry (`0`).
+    line_starts: Vec<u32>,
+}
+
+impl<'a> LineIndex<'a> {
+    /// Create a `LineIndex` for `source_text`.
+    #[allow(clippy::cast_possible_truncation)]
+    pub fn new(source_text: &'a str) -> Self {
+        let mut line_starts = vec![0];
+        let bytes = source_text.as_bytes();
+        let mut i = 0;
+        while i < bytes.len() {
+            match bytes[i] {
+                b'\n' => i += 1,
+                b'\r' => {
+                    i += if bytes.get(i + 1) == Some(&b'\n') { 2 } else { 1 };
+                }
+                // U+2028 (LS) and U+2029 (PS) are encoded as `E2 80 A8` and `E2 80 A9`
+                0xE2 if bytes[i + 1] == 0x80 && matches!(bytes[i + 2], 0xA8 | 0xA9) => i += 3,
+                _ => {
+                    i += 1;
+                    continue;
+                }
+            }
+            line_starts.push(i as u32);
+        }
+        Self { source_text, line_starts }
+    }
+
+    /// Get byte offsets of start of each line.
+    pub fn line_starts(&self) -> &[u32] {
+        &self.line_starts
+    }
+
+    /// Get number of lines in source text.
+    pub fn line_count(&self) -> usize {
+        self.line_starts.len()
+    }
+
+    /// Convert a byte offset to a line and column.
+    ///
+    /// Returns 1-based line number, and 0-based column in UTF-16 code units.
+    /// An `offset` at end of source is valid, and refers to position after the last character.
+    ///
+    /// # Panics
+    /// Panics if `offset` is past end of source text.
+    #[allow(clippy::cast_possible_truncation)]
+    pub fn line_col(&self, offset: u32) -> (u32, u32) {
+        assert!(
+            offset as usize <= self.source_text.len(),
+            "Offset {offset} is out of bounds of source text"
+        );
+
+        // `line_starts[0]` is always 0, so `line` is always at least 1
+        let line = self.line_starts.partition_point(|&start| start <= offset);
+        let line_start = self.line_starts[line - 1] as usize;
+
+        // Count UTF-16 code units of all chars which start before `offset`
+        let column = self.source_text[line_start..]
+            .char_indices()
+            .take_while(|&(index, _)| line_start + index < offset as usize)
+            .map(|(_, c)| c.len_utf16())
+            .sum::<usize>();
+
+        (line as u32, column as u32)
+    }
+}
+
+impl<'a> From<&'a str> for LineIndex<'a> {
+    fn from(source_text: &'a str) -> Self {
+        Self::new(source_text)
+    }
+}
+
+#[cfg(test)]
+#[allow(clippy::cast_possible_truncation)]
+mod test {
+    use super::LineIndex;
+
+    #[test]
+    fn line_starts() {
+        let index = LineIndex::new("a\nb\r\nc\rd\u{2028}e\u{2029}f");
+        assert_eq!(index.line_starts(), &[0, 2, 5, 7, 11, 15]);
+        assert_eq!(index.line_count(), 6);
+
+        let index = LineIndex::new("");
+        assert_eq!(index.line_starts(), &[0]);
+    }
+
+    #[test]
+    fn crlf() {
+        let source = "let x;\r\nlet y;\r\n";
+        let index = LineIndex::new(source);
+        assert_eq!(index.line_col(0), (1, 0));
+        assert_eq!(index.line_col(6), (1, 6));
+        // `\n` of `\r\n` is still on 1st line
+        assert_eq!(index.line_col(7), (1, 7));
+        assert_eq!(index.line_col(8), (2, 0));
+        assert_eq!(index.line_col(12), (2, 4));
+    }
+
+    #[test]
+    fn utf16_columns() {
+        // `é` is 2 bytes in UTF-8, 1 code unit in UTF-16
+        let source = "x\né = 1";
+        let index = LineIndex::new(source);
+        assert_eq!(index.line_col(source.find('=').unwrap() as u32), (2, 2));
+
+        // `𝓍` (U+1D4CD) is 4 bytes in UTF-8, 2 code units in UTF-16
+        let source = "x\n𝓍 = 1";
+        let index = LineIndex::new(source);
+        assert_eq!(index.line_col(source.find('=').unwrap() as u32), (2, 3));
+    }
+
+    #[test]
+    fn eof() {
+        let source = "foo\nbar";
+        let index = LineIndex::new(source);
+        assert_eq!(index.line_col(source.len() as u32), (2, 3));
+
+        let source = "foo\n";
+        let index = LineIndex::new(source);
+        assert_eq!(index.line_col(source.len() as u32), (2, 0));
+    }
+
+    #[test]
+    #[should_panic(expected = "out of bounds")]
+    fn out_of_bounds() {
+        LineIndex::new("foo").line_col(4);
+    }
+}
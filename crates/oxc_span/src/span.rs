@@ -37,6 +37,14 @@ impl Span {
         self.end - self.start
     }
 
+    /// Check if `offset` is within this span.
+    ///
+    /// Start of span is inclusive, end is exclusive. So an empty span contains no offsets.
+    #[inline]
+    pub const fn contains(&self, offset: u32) -> bool {
+        self.start <= offset && offset < self.end
+    }
+
     #[must_use]
     pub fn merge(&self, other: &Self) -> Self {
         Self::new(self.start.min(other.start), self.end.max(other.end))
@@ -69,3 +77,21 @@ impl From<Span> for LabeledSpan {
 pub trait GetSpan {
     fn span(&self) -> Span;
 }
+
+#[cfg(test)]
+mod test {
+    use super::Span;
+
+    #[test]
+    fn contains() {
+        let span = Span::new(5, 10);
+        assert!(!span.contains(4));
+        assert!(span.contains(5));
+        assert!(span.contains(9));
+        assert!(!span.contains(10));
+        assert!(!span.contains(11));
+
+        let span = Span::new(5, 5);
+        assert!(!span.contains(5));
+    }
+}
@@ -0,0 +1,38 @@
+#![no_main]
+
+use oxc_allocator::Allocator;
+use oxc_parser::lexer::{Kind, Lexer};
+use oxc_span::SourceType;
+
+libfuzzer_sys::fuzz_target!(|data: &[u8]| {
+    if let Ok(s) = std::str::from_utf8(data) {
+        // Also lex input at end of an unterminated string and template, and deeply nested in templates
+        let sources = [
+            s.to_string(),
+            format!("'\\{s}"),
+            format!("`\\{s}"),
+            format!("{}{s}{}", "`${".repeat(1000), "}`".repeat(1000)),
+        ];
+
+        let allocator = Allocator::default();
+        for source in &sources {
+            for source_type in [
+                SourceType::default(),
+                SourceType::default().with_module(true),
+                SourceType::default().with_typescript(true).with_jsx(true),
+            ] {
+                for emit_comments in [false, true] {
+                    let mut lexer = Lexer::new_recovering(&allocator, source, source_type);
+                    lexer.set_emit_comments(emit_comments);
+                    // Every token other than `Eof` consumes at least 1 byte, so lexing must terminate
+                    // within this many tokens
+                    let mut remaining = source.len();
+                    while lexer.next_token().kind != Kind::Eof {
+                        assert!(remaining > 0, "Lexer did not terminate");
+                        remaining -= 1;
+                    }
+                }
+            }
+        }
+    }
+});
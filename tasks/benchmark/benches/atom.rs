@@ -0,0 +1,129 @@
+use std::hash::{BuildHasher, BuildHasherDefault};
+
+use oxc_allocator::Allocator;
+use oxc_benchmark::{black_box, criterion_group, criterion_main, Criterion};
+use oxc_parser::lexer::{Kind, Lexer};
+use oxc_span::{Atom, CompactStr, SourceType, ATOM_MAX_INLINE_LEN};
+use oxc_tasks_common::TestFiles;
+use rustc_hash::FxHasher;
+
+/// Get text of all identifiers in test files, to give a realistic distribution of identifier lengths.
+fn identifiers(source_texts: &[String]) -> Vec<&str> {
+    let mut allocator = Allocator::default();
+    let mut identifiers = vec![];
+    for source_text in source_texts {
+        let mut lexer = Lexer::new_for_benchmarks(&allocator, source_text, SourceType::default());
+        loop {
+            let token = lexer.next_token();
+            match token.kind {
+                Kind::Eof => break,
+                Kind::Ident => {
+                    identifiers.push(&source_text[token.start as usize..token.end as usize]);
+                }
+                _ => {}
+            }
+        }
+        allocator.reset();
+    }
+    identifiers
+}
+
+fn bench_atom(criterion: &mut Criterion) {
+    // Lexer lacks awareness of JS grammar, so simplify input same as in lexer benchmark
+    let source_texts = TestFiles::complicated()
+        .files()
+        .iter()
+        .map(|file| file.source_text.replace('\\', " ").replace('`', "'"))
+        .collect::<Vec<_>>();
+    let identifiers = identifiers(&source_texts);
+
+    // `CompactStr`s of up to `ATOM_MAX_INLINE_LEN` bytes are stored inline, longer ones on heap.
+    // NB: `ATOM_MAX_INLINE_LEN` is 16 on 64-bit platforms, but only 12 on 32-bit,
+    // so more identifiers fall into the "heap" set there.
+    let (inline, heap): (Vec<&str>, Vec<&str>) =
+        identifiers.iter().partition(|s| s.len() <= ATOM_MAX_INLINE_LEN);
+
+    let atoms = identifiers.iter().map(|&s| Atom::from(s)).collect::<Vec<_>>();
+    let compact_strs = identifiers.iter().map(|&s| CompactStr::new(s)).collect::<Vec<_>>();
+
+    let mut group = criterion.benchmark_group("atom");
+
+    group.bench_function("compact_str_new_inline", |b| {
+        b.iter(|| {
+            for &s in &inline {
+                black_box(CompactStr::new(black_box(s)));
+            }
+        });
+    });
+
+    group.bench_function("compact_str_new_heap", |b| {
+        b.iter(|| {
+            for &s in &heap {
+                black_box(CompactStr::new(black_box(s)));
+            }
+        });
+    });
+
+    group.bench_function("atom_alloc_in", |b| {
+        let mut allocator = Allocator::default();
+        b.iter(|| {
+            for &s in &identifiers {
+                black_box(Atom::from(&*allocator.alloc_str(black_box(s))));
+            }
+            allocator.reset();
+        });
+    });
+
+    group.bench_function("compact_str_clone", |b| {
+        b.iter(|| {
+            for s in &compact_strs {
+                black_box(black_box(s).clone());
+            }
+        });
+    });
+
+    // Compare each identifier with the next, so some compare equal and some not,
+    // in proportion which occurs in real code
+    group.bench_function("atom_eq", |b| {
+        b.iter(|| {
+            let mut count = 0usize;
+            for pair in atoms.windows(2) {
+                count += usize::from(black_box(&pair[0]) == black_box(&pair[1]));
+            }
+            black_box(count);
+        });
+    });
+
+    group.bench_function("compact_str_eq", |b| {
+        b.iter(|| {
+            let mut count = 0usize;
+            for pair in compact_strs.windows(2) {
+                count += usize::from(black_box(&pair[0]) == black_box(&pair[1]));
+            }
+            black_box(count);
+        });
+    });
+
+    // Hash with `FxHasher`, as used for symbol tables etc
+    let build_hasher = BuildHasherDefault::<FxHasher>::default();
+    group.bench_function("atom_hash", |b| {
+        b.iter(|| {
+            for atom in &atoms {
+                black_box(build_hasher.hash_one(black_box(atom)));
+            }
+        });
+    });
+
+    group.bench_function("compact_str_hash", |b| {
+        b.iter(|| {
+            for s in &compact_strs {
+                black_box(build_hasher.hash_one(black_box(s)));
+            }
+        });
+    });
+
+    group.finish();
+}
+
+criterion_group!(atom, bench_atom);
+criterion_main!(atom);
@@ -42,5 +42,33 @@ fn bench_lexer(criterion: &mut Criterion) {
     group.finish();
 }
 
-criterion_group!(lexer, bench_lexer);
+/// Compare lexing string literals which contain no escapes (sliced directly from source)
+/// with string literals containing an escape (unescaped into arena).
+fn bench_lexer_strings(criterion: &mut Criterion) {
+    let mut group = criterion.benchmark_group("lexer_strings");
+
+    let string = "abcdefghijklmnopqrstuvwxyz".repeat(2);
+    for (name, content) in [
+        ("escape_free", format!("{string}__{string}")),
+        ("escaped", format!("{string}\\n{string}")),
+    ] {
+        let source_text = format!("'{content}';\n").repeat(1000);
+        group.bench_with_input(
+            BenchmarkId::from_parameter(name),
+            &source_text,
+            |b, source_text| {
+                let mut allocator = Allocator::default();
+                b.iter(|| {
+                    let mut lexer =
+                        Lexer::new_for_benchmarks(&allocator, source_text, SourceType::default());
+                    while lexer.next_token().kind != Kind::Eof {}
+                    allocator.reset();
+                });
+            },
+        );
+    }
+    group.finish();
+}
+
+criterion_group!(lexer, bench_lexer, bench_lexer_strings);
 criterion_main!(lexer);